@@ -0,0 +1,241 @@
+// NOTE: Singly linked stack where the nodes live in one Vec and link to each
+// other by index instead of by Box. Removed slots are threaded onto a free
+// list through the same `next` field and handed back out by the next push,
+// so the Vec only grows when every slot is in use.
+
+pub struct List<T> {
+    nodes: Vec<Node<T>>,
+    head: Link,
+    free: Link,
+    len: usize,
+}
+
+type Link = Option<usize>;
+
+struct Node<T> {
+    // NOTE: None marks a free slot, its next then points at the next free slot
+    elem: Option<T>,
+    next: Link,
+}
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List {
+            nodes: Vec::new(),
+            head: None,
+            free: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, elem: T) {
+        self.head = Some(self.alloc(elem, self.head));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.head.map(|index| {
+            self.head = self.nodes[index].next;
+            self.release(index)
+        })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.head.and_then(|index| self.nodes[index].elem.as_ref())
+    }
+
+    // NOTE: index is a position in the list, 0 being the head. index == len
+    // puts elem at the very end.
+    pub fn insert(&mut self, index: usize, elem: T) {
+        assert!(index <= self.len, "insert index {} out of bounds", index);
+        match index.checked_sub(1) {
+            None => self.push(elem),
+            Some(prev_pos) => {
+                let prev = self.slot_at(prev_pos);
+                let next = self.nodes[prev].next;
+                self.nodes[prev].next = Some(self.alloc(elem, next));
+            }
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+        match index.checked_sub(1) {
+            None => self.pop(),
+            Some(prev_pos) => {
+                let prev = self.slot_at(prev_pos);
+                let removed = self.nodes[prev].next.unwrap();
+                self.nodes[prev].next = self.nodes[removed].next;
+                Some(self.release(removed))
+            }
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: &self.nodes,
+            next: self.head,
+        }
+    }
+
+    // NOTE: Slot index of the node at a list position, position < len
+    fn slot_at(&self, pos: usize) -> usize {
+        let mut slot = self.head.unwrap();
+        for _ in 0..pos {
+            slot = self.nodes[slot].next.unwrap();
+        }
+        slot
+    }
+
+    // NOTE: Takes a slot off the free list if there is one, else grows
+    fn alloc(&mut self, elem: T, next: Link) -> usize {
+        self.len += 1;
+        let node = Node {
+            elem: Some(elem),
+            next,
+        };
+        match self.free {
+            Some(index) => {
+                self.free = self.nodes[index].next;
+                self.nodes[index] = node;
+                index
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    // NOTE: Moves the elem out and puts the slot on the free list. The caller
+    // has already unlinked it.
+    fn release(&mut self, index: usize) -> T {
+        self.len -= 1;
+        let node = &mut self.nodes[index];
+        node.next = self.free;
+        self.free = Some(index);
+        node.elem.take().unwrap()
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    nodes: &'a [Node<T>],
+    next: Link,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|index| {
+            let node = &self.nodes[index];
+            self.next = node.next;
+            node.elem.as_ref().unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+
+    #[test]
+    fn basics() {
+        let mut list = List::new();
+
+        // NOTE: Check empty list behaves right
+        assert_eq!(list.pop(), None);
+
+        // NOTE: Populate list
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // NOTE: Check normal removal
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), Some(2));
+
+        // NOTE: Push some more just to make sure nothing's corrupted
+        list.push(4);
+        list.push(5);
+
+        // NOTE: Check normal removal
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.pop(), Some(4));
+
+        // NOTE: Check exhaustion
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut list = List::new();
+        list.insert(0, 3);
+        list.insert(0, 1);
+        list.insert(1, 2);
+        list.insert(3, 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+
+        assert_eq!(list.remove(2), Some(3));
+        assert_eq!(list.remove(0), Some(1));
+        assert_eq!(list.remove(1), Some(4));
+        assert_eq!(list.remove(1), None);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(list.peek(), Some(&2));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn insert_out_of_bounds() {
+        let mut list = List::new();
+        list.push(1);
+        list.insert(2, 0);
+    }
+
+    #[test]
+    fn slot_reuse() {
+        let mut list = List::new();
+        for i in 0..5 {
+            list.push(i);
+        }
+        assert_eq!(list.nodes.len(), 5);
+
+        // NOTE: freed slots are handed out again before the Vec grows
+        assert_eq!(list.remove(1), Some(3));
+        assert_eq!(list.remove(3), Some(0));
+        list.push(10);
+        list.insert(2, 20);
+        assert_eq!(list.nodes.len(), 5);
+        list.push(30);
+        assert_eq!(list.nodes.len(), 6);
+
+        // NOTE: the links all still line up after the reuse
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![30, 10, 4, 20, 2, 1]
+        );
+        while list.pop().is_some() {}
+        assert!(list.is_empty());
+        list.push(1);
+        assert_eq!(list.nodes.len(), 6);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1]);
+    }
+}
@@ -1,8 +1,18 @@
+// NOTE: Singly linked queue, push at the tail and pop at the head, both
+// O(1). The head is a raw pointer too rather than a Box: a Box owning the
+// chain would assert unique access to every node each time it's touched,
+// which invalidates the tail pointer under stacked borrows. With raw
+// pointers everywhere the only references made are short lived ones.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
 use std::ptr;
 
 pub struct List<T> {
     head: Link<T>,
     tail: *mut Node<T>,
+    len: usize,
 }
 
 type Link<T> = *mut Node<T>;
@@ -14,6 +24,17 @@ struct Node<T> {
 
 pub struct IntoIter<T>(List<T>);
 
+// NOTE: Owns the detached chain, next is null or its first node
+pub struct Drain<'a, T> {
+    next: Link<T>,
+    _queue: PhantomData<&'a mut List<T>>,
+}
+
+// NOTE: The iterators hold real references rather than raw pointers, which
+// gives them the right lifetime and variance without any PhantomData. Each
+// reference is made fresh from a node's raw next pointer, and IterMut reads
+// the next pointer out of the node before handing out &mut elem, so the
+// element borrow never covers the pointer used to reach the next node.
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
 }
@@ -22,14 +43,89 @@ pub struct IterMut<'a, T> {
     next: Option<&'a mut Node<T>>,
 }
 
+// SAFETY: The queue owns every node outright, tail is only a second way to
+// reach the last one and is never handed out, so moving the queue to another
+// thread moves all of it and leaves nothing behind to alias. Shared access
+// only ever reads through &self, same as an owned Vec<T>.
+unsafe impl<T: Send> Send for List<T> {}
+unsafe impl<T: Sync> Sync for List<T> {}
+
+// SAFETY: The iterators are really just &T and &mut T, the raw next pointers
+// inside the nodes are only there to find the following element. They get
+// the same bounds as those references.
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+// SAFETY: A Drain owns the nodes it detached, same as the queue did
+unsafe impl<T: Send> Send for Drain<'_, T> {}
+unsafe impl<T: Sync> Sync for Drain<'_, T> {}
+
+// NOTE: These fail to compile if the bounds above are ever loosened or lost.
+// The not_send/not_sync checks only resolve when the trait isn't implemented,
+// otherwise the call is ambiguous between the two impls.
+const _: () = {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    fn assert_send<S: Send>() {}
+    fn assert_sync<S: Sync>() {}
+    fn check<T: Send + Sync>() {
+        assert_send::<List<T>>();
+        assert_sync::<List<T>>();
+        assert_send::<IntoIter<T>>();
+        assert_send::<Iter<'_, T>>();
+        assert_sync::<Iter<'_, T>>();
+        assert_send::<IterMut<'_, T>>();
+        assert_sync::<IterMut<'_, T>>();
+    }
+    // NOTE: Cell is Send but not Sync
+    fn check_send_only() {
+        assert_send::<List<Cell<i32>>>();
+        assert_send::<IterMut<'_, Cell<i32>>>();
+    }
+
+    trait NotSend<A> {
+        fn check() {}
+    }
+    impl<T: ?Sized> NotSend<()> for T {}
+    impl<T: ?Sized + Send> NotSend<u8> for T {}
+    trait NotSync<A> {
+        fn check() {}
+    }
+    impl<T: ?Sized> NotSync<()> for T {}
+    impl<T: ?Sized + Sync> NotSync<u8> for T {}
+
+    let _ = check::<i32>;
+    let _ = check_send_only;
+    let _ = <List<Rc<i32>> as NotSend<_>>::check;
+    let _ = <List<Rc<i32>> as NotSync<_>>::check;
+    let _ = <List<Cell<i32>> as NotSync<_>>::check;
+    let _ = <Iter<'_, Cell<i32>> as NotSend<_>>::check;
+    let _ = <IterMut<'_, Rc<i32>> as NotSend<_>>::check;
+    let _ = <IterMut<'_, Cell<i32>> as NotSync<_>>::check;
+};
+
 impl<T> List<T> {
     pub fn new() -> Self {
         List {
             head: ptr::null_mut(),
             tail: ptr::null_mut(),
+            len: 0,
         }
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn push(&mut self, elem: T) {
+        // SAFETY: tail is either null or the last node we allocated, which
+        // stays alive until pop frees it and resets tail if it was the last.
         unsafe {
             let new_tail = Box::into_raw(Box::new(Node {
                 elem,
@@ -43,16 +139,36 @@ impl<T> List<T> {
             }
 
             self.tail = new_tail;
+            self.len += 1;
+        }
+    }
+    // NOTE: Lets it work as a deque that only pops from the front. An empty
+    // queue has to point tail at the new node too, or the next push would
+    // think there's nothing to link onto and overwrite head.
+    pub fn push_front(&mut self, elem: T) {
+        let new_head = Box::into_raw(Box::new(Node {
+            elem,
+            next: self.head,
+        }));
+        if self.tail.is_null() {
+            self.tail = new_head;
         }
+        self.head = new_head;
+        self.len += 1;
     }
+
     pub fn pop(&mut self) -> Option<T> {
+        // SAFETY: a non null head came from Box::into_raw in push and nothing
+        // else frees it, so taking the Box back is the one and only free.
         unsafe {
             if self.head.is_null() {
                 None
             } else {
                 let head = Box::from_raw(self.head);
                 self.head = head.next;
+                self.len -= 1;
 
+                // NOTE: that was the last node, tail would be left dangling
                 if self.head.is_null() {
                     self.tail = ptr::null_mut();
                 }
@@ -62,19 +178,132 @@ impl<T> List<T> {
         }
     }
 
+    // NOTE: Moves other's whole chain onto our tail without walking it,
+    // other is left empty and ready to use again.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.head.is_null() {
+            return;
+        }
+        if self.tail.is_null() {
+            self.head = other.head;
+        } else {
+            // SAFETY: a non null tail is our live last node, same as in push
+            unsafe {
+                (*self.tail).next = other.head;
+            }
+        }
+        self.tail = other.tail;
+        self.len += other.len;
+        other.head = ptr::null_mut();
+        other.tail = ptr::null_mut();
+        other.len = 0;
+    }
+
+    // NOTE: Keeps the first at elements and returns the rest, which keeps
+    // our old tail. Panics if at > len, like Vec::split_off.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split_off index {} out of bounds", at);
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == self.len {
+            return List::new();
+        }
+
+        // SAFETY: 0 < at < len, so the walk stays on live nodes and the one
+        // it stops at, our new last node, has a next
+        unsafe {
+            let mut last = self.head;
+            for _ in 1..at {
+                last = (*last).next;
+            }
+            let back = List {
+                head: (*last).next,
+                tail: self.tail,
+                len: self.len - at,
+            };
+            (*last).next = ptr::null_mut();
+            self.tail = last;
+            self.len = at;
+            back
+        }
+    }
+
+    // NOTE: The other half of split_off, returns the first n and keeps the
+    // rest. Asking for more than there is takes everything.
+    pub fn take_front(&mut self, n: usize) -> Self {
+        let back = self.split_off(n.min(self.len));
+        mem::replace(self, back)
+    }
+
+    // NOTE: One pass. prev is the last node kept so far (null while there
+    // are none), so dropping a leading run moves head along and dropping a
+    // trailing run pulls tail back to prev. Every link is fixed before the
+    // node is freed, so the queue is consistent even if f or a drop panics.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut prev: *mut Node<T> = ptr::null_mut();
+        let mut cur = self.head;
+        // SAFETY: cur walks the live chain from head. A node is only freed
+        // after it's been unlinked, and we'd already read its next.
+        unsafe {
+            while !cur.is_null() {
+                let next = (*cur).next;
+                if f(&(*cur).elem) {
+                    prev = cur;
+                } else {
+                    if prev.is_null() {
+                        self.head = next;
+                    } else {
+                        (*prev).next = next;
+                    }
+                    if next.is_null() {
+                        self.tail = prev;
+                    }
+                    self.len -= 1;
+                    drop(Box::from_raw(cur));
+                }
+                cur = next;
+            }
+        }
+    }
+
+    // NOTE: The whole chain is taken off the queue up front, so it's empty
+    // and its tail is null straight away whatever happens to the Drain. The
+    // Drain still holds the &mut borrow, which keeps pushes out until it's
+    // gone:
+    /// ```compile_fail,E0499
+    /// let mut list: linked_lists::fifth::List<i32> = (1..=3).collect();
+    /// let drain = list.drain();
+    /// list.push(4);
+    /// drop(drain);
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        let next = self.head;
+        self.head = ptr::null_mut();
+        self.tail = ptr::null_mut();
+        self.len = 0;
+        Drain {
+            next,
+            _queue: PhantomData,
+        }
+    }
+
+    // NOTE: The references live as long as the borrow of self, and push,
+    // the only thing that writes through tail, needs &mut self. So even when
+    // head and tail are the same node they can never be used at once.
     pub fn peek(&self) -> Option<&T> {
+        // SAFETY: head is null or a live node owned by the queue
         unsafe { self.head.as_ref().map(|node| &node.elem) }
     }
 
     pub fn peek_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in peek, and &mut self means nothing else is reading it
         unsafe { self.head.as_mut().map(|node| &mut node.elem) }
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
-    }
-
     pub fn iter(&self) -> Iter<'_, T> {
+        // SAFETY: as in peek, the shared borrow of self keeps the nodes alive
+        // and unwritten for as long as the Iter
         unsafe {
             Iter {
                 next: self.head.as_ref(),
@@ -83,6 +312,8 @@ impl<T> List<T> {
     }
 
     pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        // SAFETY: as in peek_mut, the IterMut holds the &mut self borrow so
+        // nothing else can touch the nodes while it's around
         unsafe {
             IterMut {
                 next: self.head.as_mut(),
@@ -91,9 +322,59 @@ impl<T> List<T> {
     }
 }
 
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// NOTE: Both go through iter(), so no unsafe of their own
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+// NOTE: Pushes at the tail so the elements queue up in iterator order
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push(elem);
+        }
+    }
+}
+
+// NOTE: Each pop frees exactly one Box in a loop, and tail is only ever
+// read by push, so it's never followed once the nodes are gone. IntoIter
+// just wraps the list and gets this for free.
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        while let Some(_) = self.pop() {}
+        while self.pop().is_some() {}
+    }
+}
+
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
     }
 }
 
@@ -102,12 +383,40 @@ impl<T> Iterator for IntoIter<T> {
     fn next(&mut self) -> Option<Self::Item> {
         self.0.pop()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_null() {
+            return None;
+        }
+        // SAFETY: the detached nodes all came from Box::into_raw in push and
+        // only the Drain can reach them now, each is freed once as we pass
+        let node = unsafe { Box::from_raw(self.next) };
+        self.next = node.next;
+        Some(node.elem)
+    }
+}
+
+// NOTE: Frees whatever wasn't handed out, the queue itself is already empty
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        while self.next().is_some() {}
+    }
 }
 
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: next is null or a live node of the borrowed queue, and so
+        // is every node linked after it
         unsafe {
             self.next.map(|node| {
                 self.next = node.next.as_ref();
@@ -121,6 +430,8 @@ impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: as in Iter::next. Each node is handed out once, take()
+        // moves on past it, so no two &mut to the same elem exist.
         unsafe {
             self.next.take().map(|node| {
                 self.next = node.next.as_mut();
@@ -214,6 +525,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::option_map_unit_fn)]
     fn miri_food() {
         let mut list = List::new();
 
@@ -250,4 +562,528 @@ mod test {
 
         // NOTE: Drop it on the ground and let the dtor exercise itself
     }
+
+    #[test]
+    fn interleaved() {
+        let mut list = List::new();
+
+        // NOTE: the second.rs push/pop pattern, FIFO order this time
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.pop(), Some(1));
+        list.push(3);
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+
+        // NOTE: empty it and fill it back up over and over, each time the
+        // tail has to be reset or the next push writes through a freed node
+        for round in 0..10 {
+            for i in 0..round {
+                list.push(i);
+            }
+            for i in 0..round {
+                assert_eq!(list.pop(), Some(i));
+            }
+            assert_eq!(list.pop(), None);
+            assert!(list.tail.is_null());
+            list.push(round);
+            assert_eq!(list.pop(), Some(round));
+        }
+    }
+
+    #[test]
+    fn long_drop() {
+        // NOTE: Miri is far too slow for the full million
+        let n = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let mut list = List::new();
+        for i in 0..n {
+            list.push(Box::new(i));
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn peek() {
+        let mut list = List::new();
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.peek(), Some(&1));
+        if let Some(x) = list.peek_mut() {
+            *x = 10;
+        }
+        assert_eq!(list.pop(), Some(10));
+        assert_eq!(list.peek(), Some(&2));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.peek(), None);
+        assert_eq!(list.peek_mut(), None);
+    }
+
+    #[test]
+    fn peek_with_tail() {
+        // NOTE: with one element head and tail are the same node, so this
+        // mixes the peek references with writes through the tail pointer.
+        // Miri checks that neither invalidates the other.
+        let mut list = List::new();
+        list.push(1);
+        if let Some(x) = list.peek_mut() {
+            *x += 1;
+        }
+        list.push(3);
+        assert_eq!(list.peek(), Some(&2));
+        if let Some(x) = list.peek_mut() {
+            *x *= 10;
+        }
+        list.push(4);
+        assert_eq!(list.pop(), Some(20));
+
+        // NOTE: and again after the tail moved on past the head
+        assert_eq!(list.peek(), Some(&3));
+        list.push(5);
+        assert_eq!(list.peek(), Some(&3));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn iter_mut_then_iter() {
+        let mut list = List::new();
+        for i in 1..=4 {
+            list.push(i);
+        }
+        for elem in list.iter_mut() {
+            *elem *= 10;
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![10, 20, 30, 40]
+        );
+
+        // NOTE: keep every &mut at once, each one has to stay valid
+        let all: Vec<&mut i32> = list.iter_mut().collect();
+        for elem in all {
+            *elem += 1;
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![11, 21, 31, 41]
+        );
+
+        // NOTE: exhausted iterators stay exhausted
+        let mut iter = list.iter();
+        assert_eq!(iter.by_ref().count(), 4);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.by_ref().count(), 4);
+        assert_eq!(iter.next(), None);
+
+        // NOTE: and the queue still works afterwards
+        list.push(5);
+        assert_eq!(list.iter().last(), Some(&5));
+        assert_eq!(list.pop(), Some(11));
+
+        let mut empty: List<i32> = List::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.iter_mut().next(), None);
+    }
+
+    #[test]
+    fn drop_counts() {
+        use std::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+        let filled = |drops| {
+            let mut list = List::new();
+            for _ in 0..5 {
+                list.push(Counted(drops));
+            }
+            list
+        };
+
+        // NOTE: handed out one at a time, dropped by the caller
+        let drops = Cell::new(0);
+        for (i, elem) in filled(&drops).into_iter().enumerate() {
+            assert_eq!(drops.get(), i);
+            drop(elem);
+        }
+        assert_eq!(drops.get(), 5);
+
+        // NOTE: the iterator frees whatever it didn't get to
+        let drops = Cell::new(0);
+        let mut iter = filled(&drops).into_iter();
+        drop(iter.next());
+        drop(iter.next());
+        assert_eq!(drops.get(), 2);
+        drop(iter);
+        assert_eq!(drops.get(), 5);
+
+        // NOTE: never iterated at all, including after being emptied and
+        // refilled so the tail had to be reset in between
+        let drops = Cell::new(0);
+        let mut list = filled(&drops);
+        while list.pop().is_some() {}
+        list.push(Counted(&drops));
+        list.push(Counted(&drops));
+        assert_eq!(drops.get(), 5);
+        drop(list);
+        assert_eq!(drops.get(), 7);
+    }
+
+    #[test]
+    fn collect_extend() {
+        let mut list: List<i32> = (1..=3).collect();
+        assert_eq!(list.peek(), Some(&1));
+        list.extend(vec![4, 5]);
+        list.extend(std::iter::empty());
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(list.pop(), Some(1));
+
+        let mut empty: List<i32> = std::iter::empty().collect();
+        assert_eq!(empty.pop(), None);
+        empty.extend(Some(1));
+        assert_eq!(empty.pop(), Some(1));
+    }
+
+    #[test]
+    fn append() {
+        let mut list: List<i32> = (1..=3).collect();
+        let mut other: List<i32> = (4..=5).collect();
+        list.append(&mut other);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(other.head.is_null() && other.tail.is_null());
+
+        // NOTE: our tail is other's old one now, and other is usable again
+        list.push(6);
+        other.push(7);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert_eq!(other.iter().copied().collect::<Vec<_>>(), vec![7]);
+
+        // NOTE: all the empty combinations keep head and tail in step
+        let mut list = List::new();
+        list.append(&mut other);
+        assert!(other.head.is_null() && other.tail.is_null());
+        list.push(8);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![7, 8]);
+
+        list.append(&mut List::new());
+        list.push(9);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![7, 8, 9]);
+
+        let mut list: List<i32> = List::new();
+        list.append(&mut List::new());
+        assert!(list.head.is_null() && list.tail.is_null());
+        list.push(1);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        // NOTE: drain it through the appended join
+        let mut list: List<i32> = (1..=2).collect();
+        list.append(&mut (3..=4).collect());
+        while list.pop().is_some() {}
+        assert!(list.tail.is_null());
+    }
+
+    #[test]
+    fn len() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        // NOTE: mirror every operation on a VecDeque and compare lengths
+        let mut model = std::collections::VecDeque::new();
+        for i in 0..200 {
+            if i % 3 == 2 {
+                assert_eq!(list.pop(), model.pop_front());
+            } else {
+                list.push(i);
+                model.push_back(i);
+            }
+            if i % 50 == 49 {
+                let mut other: List<i32> = (0..i % 7).collect();
+                model.extend(0..i % 7);
+                list.append(&mut other);
+                assert_eq!(other.len(), 0);
+            }
+            assert_eq!(list.len(), model.len());
+        }
+        while list.pop().is_some() {}
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        let list: List<i32> = (0..4).collect();
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (4, Some(4)));
+        iter.next();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn debug_eq() {
+        let list: List<i32> = (1..=3).collect();
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+
+        // NOTE: built different ways, still equal
+        let mut other = List::new();
+        other.push(0);
+        other.push(1);
+        other.pop();
+        other.extend(vec![2, 3]);
+        assert_eq!(list, other);
+
+        if let Some(x) = other.iter_mut().last() {
+            *x = 4;
+        }
+        assert_ne!(list, other);
+        other.pop();
+        assert_ne!(list, other);
+        assert_eq!(List::<i32>::new(), List::new());
+    }
+
+    #[test]
+    fn send_to_thread() {
+        let mut list: List<String> = (1..=3).map(|i| i.to_string()).collect();
+        let handle = std::thread::spawn(move || {
+            assert_eq!(list.pop().as_deref(), Some("1"));
+            list.push(String::from("4"));
+            list
+        });
+        let mut list = handle.join().unwrap();
+        list.push(String::from("5"));
+        assert_eq!(format!("{:?}", list), r#"["2", "3", "4", "5"]"#);
+
+        // NOTE: and shared between threads by reference
+        let total: usize = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..2)
+                .map(|_| scope.spawn(|| list.iter().map(|s| s.len()).sum::<usize>()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).sum()
+        });
+        assert_eq!(total, 8);
+    }
+
+    #[test]
+    fn drain() {
+        let mut list: List<i32> = (1..=4).collect();
+        assert_eq!(list.drain().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(list.is_empty());
+        assert!(list.head.is_null() && list.tail.is_null());
+
+        // NOTE: partly consumed, the queue is still left empty
+        let mut list: List<i32> = (1..=4).collect();
+        let mut drain = list.drain();
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next(), Some(2));
+        drop(drain);
+        assert!(list.is_empty());
+        assert!(list.head.is_null() && list.tail.is_null());
+
+        // NOTE: and straight away usable again
+        list.push(5);
+        list.push(6);
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.len(), 1);
+
+        let mut empty: List<i32> = List::new();
+        assert_eq!(empty.drain().next(), None);
+    }
+
+    #[test]
+    fn drain_drop_counts() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..5 {
+            list.push(probe.clone());
+        }
+        let mut drain = list.drain();
+        let first = drain.next();
+        assert_eq!(Rc::strong_count(&probe), 6);
+        drop(drain);
+        assert_eq!(Rc::strong_count(&probe), 2);
+        drop(first);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn retain() {
+        let check = |keep: fn(&i32) -> bool, expected: Vec<i32>| {
+            let mut list: List<i32> = (1..=6).collect();
+            list.retain(keep);
+            assert_eq!(list.len(), expected.len());
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+            // NOTE: a push lands after the right tail
+            list.push(7);
+            let mut expected = expected;
+            expected.push(7);
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+            while list.pop().is_some() {}
+            assert!(list.head.is_null() && list.tail.is_null());
+        };
+
+        check(|&x| x > 2, vec![3, 4, 5, 6]);
+        check(|&x| x < 5, vec![1, 2, 3, 4]);
+        check(|&x| x == 1 || x == 6, vec![1, 6]);
+        check(|&x| x % 2 == 0, vec![2, 4, 6]);
+        check(|&x| x == 3, vec![3]);
+        check(|_| true, vec![1, 2, 3, 4, 5, 6]);
+        check(|_| false, vec![]);
+
+        let mut empty: List<i32> = List::new();
+        empty.retain(|_| unreachable!());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn retain_drops_now() {
+        use std::rc::Rc;
+
+        let keep = Rc::new(());
+        let toss = Rc::new(());
+        let mut list = List::new();
+        for i in 0..6 {
+            list.push(if i % 2 == 0 {
+                keep.clone()
+            } else {
+                toss.clone()
+            });
+        }
+
+        // NOTE: the last one tossed is freed before the next is even looked at
+        let mut seen = Vec::new();
+        list.retain(|rc| {
+            seen.push(Rc::strong_count(&toss));
+            Rc::ptr_eq(rc, &keep)
+        });
+        assert_eq!(seen, vec![4, 4, 3, 3, 2, 2]);
+        assert_eq!(Rc::strong_count(&toss), 1);
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn split_off() {
+        let mut list: List<i32> = (1..=5).collect();
+        let mut back = list.split_off(2);
+        assert_eq!((list.len(), back.len()), (2, 3));
+
+        // NOTE: both tails are live, pushes land on the right ends
+        list.push(10);
+        back.push(20);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 10]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 20]);
+
+        let mut list: List<i32> = (1..=3).collect();
+        let mut back = list.split_off(0);
+        assert!(list.is_empty() && list.head.is_null() && list.tail.is_null());
+        list.push(0);
+        back.push(4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        let mut list: List<i32> = (1..=3).collect();
+        let mut back = list.split_off(3);
+        assert!(back.is_empty());
+        list.push(4);
+        back.push(5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![5]);
+
+        // NOTE: one each side, the cut is right next to both ends
+        let mut list: List<i32> = (1..=2).collect();
+        let back = list.split_off(1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn split_off_out_of_bounds() {
+        let mut list: List<i32> = (1..=2).collect();
+        list.split_off(3);
+    }
+
+    #[test]
+    fn take_front() {
+        let mut list: List<i32> = (1..=5).collect();
+        let mut front = list.take_front(2);
+        assert_eq!((front.len(), list.len()), (2, 3));
+        front.push(10);
+        list.push(20);
+        assert_eq!(front.iter().copied().collect::<Vec<_>>(), vec![1, 2, 10]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 20]);
+
+        let mut front = list.take_front(0);
+        assert!(front.is_empty());
+        front.push(1);
+        assert_eq!(front.pop(), Some(1));
+        assert_eq!(list.len(), 4);
+
+        let mut front = list.take_front(10);
+        assert!(list.is_empty() && list.tail.is_null());
+        list.push(30);
+        front.push(40);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![30]);
+        assert_eq!(
+            front.iter().copied().collect::<Vec<_>>(),
+            vec![3, 4, 5, 20, 40]
+        );
+    }
+
+    #[test]
+    fn push_front() {
+        // NOTE: empty to one through push_front, then the tail has to work
+        let mut list = List::new();
+        list.push_front(2);
+        list.push(3);
+        list.push_front(1);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+        assert!(list.tail.is_null());
+
+        // NOTE: through empty over and over from either end
+        for i in 0..10 {
+            list.push_front(i);
+            assert_eq!(list.pop(), Some(i));
+            list.push_front(i);
+            list.push(i + 1);
+            list.push_front(i - 1);
+            assert_eq!(list.pop(), Some(i - 1));
+            assert_eq!(list.pop(), Some(i));
+            list.push_front(i + 2);
+            assert_eq!(list.pop(), Some(i + 2));
+            assert_eq!(list.pop(), Some(i + 1));
+            assert_eq!(list.pop(), None);
+            assert_eq!(list.len(), 0);
+        }
+
+        let mut list: List<i32> = List::new();
+        list.push_front(1);
+        let mut other = List::new();
+        other.push_front(2);
+        list.append(&mut other);
+        list.push(3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
 }
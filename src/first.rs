@@ -1,5 +1,6 @@
 // NOTE: Bad singly linked stack
 
+use std::fmt;
 use std::mem;
 
 pub struct List {
@@ -52,6 +53,62 @@ impl List {
             }
         }
     }
+
+    // NOTE: No cached length, we just walk the links and count
+    pub fn len(&self) -> usize {
+        let mut count = 0;
+        let mut cur_link = &self.head;
+        while let Link::More(node) = cur_link {
+            count += 1;
+            cur_link = &node.next;
+        }
+        count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self.head, Link::Empty)
+    }
+}
+
+impl Default for List {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for List {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries = f.debug_list();
+        let mut cur_link = &self.head;
+        while let Link::More(node) = cur_link {
+            entries.entry(&node.elem);
+            cur_link = &node.next;
+        }
+        entries.finish()
+    }
+}
+
+// NOTE: Extend appends at the back, so the items keep their iterator order
+// behind whatever is already in the list.
+impl Extend<i32> for List {
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        // NOTE: walk to the Empty link at the end of the list
+        let mut cur_link = &mut self.head;
+        while let Link::More(node) = cur_link {
+            cur_link = &mut node.next;
+        }
+
+        for elem in iter {
+            let new_node = Box::new(Node {
+                elem,
+                next: mem::replace(cur_link, Link::Empty),
+            });
+            *cur_link = Link::More(new_node);
+            if let Link::More(node) = cur_link {
+                cur_link = &mut node.next;
+            }
+        }
+    }
 }
 
 // NOTE: setup tests
@@ -88,4 +145,54 @@ mod test {
         assert_eq!(list.pop(), Some(1));
         assert_eq!(list.pop(), None);
     }
+
+    #[test]
+    fn test_debug() {
+        let mut list = List::default();
+        assert_eq!(format!("{:?}", list), "[]");
+
+        list.push(1);
+        list.push(2);
+        list.push(3);
+
+        // NOTE: front-to-back, so the last push is printed first
+        assert_eq!(format!("{:?}", list), "[3, 2, 1]");
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut list = List::new();
+        list.push(0);
+        list.extend(1..4);
+
+        // NOTE: extended items come after the existing ones, in order
+        assert_eq!(format!("{:?}", list), "[0, 1, 2, 3]");
+        assert_eq!(list.pop(), Some(0));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+
+        list.pop();
+        assert_eq!(list.len(), 1);
+        list.pop();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        // NOTE: popping an empty list leaves the length alone
+        list.pop();
+        assert_eq!(list.len(), 0);
+    }
 }
@@ -1,11 +1,15 @@
 // Bad but safe implementation of a doubly linked dequeue
 
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{BorrowError, BorrowMutError, Ref, RefCell, RefMut};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
 use std::rc::Rc;
 
 pub struct List<T> {
     head: Link<T>,
     tail: Link<T>,
+    len: usize,
 }
 
 type Link<T> = Option<Rc<RefCell<Node<T>>>>;
@@ -26,17 +30,54 @@ impl<T> Node<T> {
     }
 }
 
+// NOTE: Why try_pop_front/try_pop_back left the list alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryPopError {
+    // NOTE: a node the pop has to relink already has a guard out
+    Borrowed,
+    // NOTE: the node being popped has an Rc outside the list, so its element
+    // can't be moved out of it
+    Shared,
+}
+
+impl fmt::Display for TryPopError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryPopError::Borrowed => write!(f, "node is already borrowed"),
+            TryPopError::Shared => write!(f, "node is shared outside the list"),
+        }
+    }
+}
+
+impl std::error::Error for TryPopError {}
+
 impl<T> List<T> {
     pub fn new() -> Self {
         List {
             head: None,
             tail: None,
+            len: 0,
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // NOTE: Pops everything so each node's links are broken as it goes, the
+    // list is empty and ready to use again afterwards.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
     pub fn push_front(&mut self, elem: T) {
         // NOTE: new node needs +2 links everything else should be +0
         let new_head = Node::new(elem);
+        self.len += 1;
         match self.head.take() {
             Some(old_head) => {
                 // NOTE: Empty list need to connect old list
@@ -57,6 +98,7 @@ impl<T> List<T> {
     pub fn pop_front(&mut self) -> Option<T> {
         // NOTE: need to take the old head ensuring its -2
         self.head.take().map(|old_head| {
+            self.len -= 1;
             match old_head.borrow_mut().next.take() {
                 Some(new_head) => {
                     // NOTE: not emptying list
@@ -75,6 +117,7 @@ impl<T> List<T> {
     pub fn push_back(&mut self, elem: T) {
         // NOTE: new node needs +2 links everything else should be +0
         let new_tail = Node::new(elem);
+        self.len += 1;
         match self.tail.take() {
             Some(old_tail) => {
                 // NOTE: Empty list need to connect old list
@@ -94,6 +137,7 @@ impl<T> List<T> {
 
     pub fn pop_back(&mut self) -> Option<T> {
         self.tail.take().map(|old_tail| {
+            self.len -= 1;
             match old_tail.borrow_mut().prev.take() {
                 Some(new_tail) => {
                     // NOTE: not emptying list
@@ -109,43 +153,508 @@ impl<T> List<T> {
         })
     }
 
-    pub fn peek_front(&self) -> Option<Ref<T>> {
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor::new(self, self.head.clone(), 0)
+    }
+
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor::new(self, self.tail.clone(), self.len.saturating_sub(1))
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        let current = self.head.clone();
+        let index = current.as_ref().map(|_| 0);
+        CursorMut {
+            list: self,
+            current,
+            index,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        let current = self.tail.clone();
+        let index = current.as_ref().map(|_| self.len - 1);
+        CursorMut {
+            list: self,
+            current,
+            index,
+        }
+    }
+
+    // NOTE: Each failing node is unlinked and its elem dropped on the spot
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut cursor = self.cursor_front_mut();
+        while let Some(keep) = cursor.current().map(|elem| f(&elem)) {
+            if keep {
+                cursor.move_next();
+            } else {
+                cursor.remove_current();
+            }
+        }
+    }
+
+    // NOTE: Whatever the Drain doesn't hand out is dropped along with it, so
+    // the list is always empty afterwards.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain(self)
+    }
+
+    // NOTE: Hooks other's chain onto our tail without touching any of the
+    // nodes in between, other is left empty.
+    pub fn append(&mut self, other: &mut Self) {
+        let Some(old_tail) = self.tail.take() else {
+            // NOTE: Nothing of ours to link to, just take other's whole chain
+            mem::swap(self, other);
+            return;
+        };
+        match other.head.take() {
+            Some(other_head) => {
+                other_head.borrow_mut().prev = Some(old_tail.clone());
+                old_tail.borrow_mut().next = Some(other_head);
+                self.tail = other.tail.take();
+                self.len += mem::replace(&mut other.len, 0);
+            }
+            None => self.tail = Some(old_tail),
+        }
+    }
+
+    // NOTE: Keeps [0, at) and returns [at, len) as a new list, like
+    // Vec::split_off. Panics if at > len.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len, "split_off index {} out of bounds", at);
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == self.len {
+            return List::new();
+        }
+
+        // NOTE: Walk to the last node we keep, 0 < at < len so it and the
+        // node after it both exist
+        let mut last = self.head.clone().unwrap();
+        for _ in 1..at {
+            let next = last.borrow().next.clone().unwrap();
+            last = next;
+        }
+        let new_head = last.borrow_mut().next.take().unwrap();
+        new_head.borrow_mut().prev = None;
+
+        let split = List {
+            head: Some(new_head),
+            tail: self.tail.replace(last),
+            len: self.len - at,
+        };
+        self.len = at;
+        split
+    }
+
+    // NOTE: The peeks hand out RefCell guards mapped down to just the elem.
+    // The node stays borrowed for as long as a guard lives. Pushes and pops
+    // take &mut self so the compiler stops those overlapping a guard, but the
+    // _mut peeks only need &self: asking for a mut guard on a node that
+    // already has a guard out (e.g. front and back of a one element list)
+    // panics with a borrow error instead of aliasing.
+    pub fn peek_front(&self) -> Option<Ref<'_, T>> {
         self.head
             .as_ref()
             .map(|node| Ref::map(node.borrow(), |node| &node.elem))
     }
 
-    pub fn peek_front_mut(&self) -> Option<RefMut<T>> {
+    pub fn peek_front_mut(&self) -> Option<RefMut<'_, T>> {
         self.head
             .as_ref()
             .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
     }
 
-    pub fn peek_back(&self) -> Option<Ref<T>> {
+    pub fn peek_back(&self) -> Option<Ref<'_, T>> {
         self.tail
             .as_ref()
             .map(|node| Ref::map(node.borrow(), |node| &node.elem))
     }
 
-    pub fn peek_back_mut(&self) -> Option<RefMut<T>> {
+    pub fn peek_back_mut(&self) -> Option<RefMut<'_, T>> {
         self.tail
             .as_ref()
             .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        let mut vec = Vec::with_capacity(self.len);
+        vec.extend(self.iter().map(|elem| elem.borrow().clone()));
+        vec
+    }
+
+    // NOTE: try_ versions of the peeks, these give back the RefCell's
+    // borrow error instead of panicking when the node already has a
+    // conflicting guard out. Ok(None) means the list is empty.
+    pub fn try_peek_front(&self) -> Result<Option<Ref<'_, T>>, BorrowError> {
+        self.head
+            .as_ref()
+            .map(|node| {
+                node.try_borrow()
+                    .map(|node| Ref::map(node, |node| &node.elem))
+            })
+            .transpose()
+    }
+
+    pub fn try_peek_front_mut(&self) -> Result<Option<RefMut<'_, T>>, BorrowMutError> {
+        self.head
+            .as_ref()
+            .map(|node| {
+                node.try_borrow_mut()
+                    .map(|node| RefMut::map(node, |node| &mut node.elem))
+            })
+            .transpose()
+    }
+
+    pub fn try_peek_back(&self) -> Result<Option<Ref<'_, T>>, BorrowError> {
+        self.tail
+            .as_ref()
+            .map(|node| {
+                node.try_borrow()
+                    .map(|node| Ref::map(node, |node| &node.elem))
+            })
+            .transpose()
+    }
+
+    pub fn try_peek_back_mut(&self) -> Result<Option<RefMut<'_, T>>, BorrowMutError> {
+        self.tail
+            .as_ref()
+            .map(|node| {
+                node.try_borrow_mut()
+                    .map(|node| RefMut::map(node, |node| &mut node.elem))
+            })
+            .transpose()
+    }
+
+    // NOTE: Guards borrow the list so none can be alive across a &mut self
+    // call through the public API, but a borrow or an Rc held on a node some
+    // other way would make pop_* panic halfway through. These check every
+    // node the pop would borrow_mut first, and that the popped node is only
+    // held by the list, and leave the list untouched if not.
+    pub fn try_pop_front(&mut self) -> Result<Option<T>, TryPopError> {
+        if let Some(head) = &self.head {
+            Self::check_pop(head, |node| &node.next)?;
+        }
+        Ok(self.pop_front())
+    }
+
+    pub fn try_pop_back(&mut self) -> Result<Option<T>, TryPopError> {
+        if let Some(tail) = &self.tail {
+            Self::check_pop(tail, |node| &node.prev)?;
+        }
+        Ok(self.pop_back())
+    }
+
+    fn check_pop(
+        node: &Rc<RefCell<Node<T>>>,
+        neighbour: fn(&Node<T>) -> &Link<T>,
+    ) -> Result<(), TryPopError> {
+        let node_ref = node.try_borrow_mut().map_err(|_| TryPopError::Borrowed)?;
+        if let Some(other) = neighbour(&node_ref) {
+            other.try_borrow_mut().map_err(|_| TryPopError::Borrowed)?;
+        }
+        // NOTE: one Rc from the end of the list and one from the neighbour's
+        // link back, or from the other end if it's the only node
+        if Rc::strong_count(node) != 2 {
+            return Err(TryPopError::Shared);
+        }
+        Ok(())
+    }
+
+    // NOTE: Iterating hands out one Elem per element, and Elem::borrow gives
+    // its Ref guard. Any number of those can be alive at once since they're
+    // all shared borrows, but an element with a guard still out can't be
+    // borrowed mutably: a peek_*_mut or ElemMut::borrow_mut on it panics.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.clone(),
+            _list: PhantomData,
+        }
+    }
+
+    // NOTE: Same deal with ElemMut and RefMut guards. It takes &self on
+    // purpose, like the _mut peeks: every RefMut is already checked by its
+    // RefCell at runtime, so all the list borrow has to do is stop nodes
+    // being unlinked under the iterator, and &self does that while still
+    // letting iter_mut sit alongside peeks and other iterators. Borrowing an
+    // element mutably while it has any other guard out (from iter, a peek,
+    // or another iter_mut) panics.
+    pub fn iter_mut(&self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.clone(),
+            _list: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// NOTE: These all walk with iter(), so each node is only borrowed while its
+// own element is being looked at and shared guards handed out elsewhere are
+// fine. Anything holding a mut guard makes them panic like iter() would.
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for elem in self.iter() {
+            list.entry(&*elem.borrow());
+        }
+        list.finish()
     }
 }
 
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| *a.borrow() == *b.borrow())
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+// NOTE: Builds fresh nodes, the clone shares no Rc with the original
+impl<T: Clone> Clone for List<T> {
+    fn clone(&self) -> Self {
+        let mut list = List::new();
+        for elem in self.iter() {
+            list.push_back(elem.borrow().clone());
+        }
+        list
+    }
+}
+
+// NOTE: Index 0 ends up at the front
+impl<T> From<Vec<T>> for List<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+// NOTE: Goes on the back in iterator order
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+// NOTE: Moves the elements out through pop_front, which unwraps each node
+// rather than cloning. The rest stay owned by the list the whole time, so if
+// anything unwinds partway they're still dropped exactly once.
+impl<T> From<List<T>> for Vec<T> {
+    fn from(mut list: List<T>) -> Self {
+        let mut vec = Vec::with_capacity(list.len);
+        while let Some(elem) = list.pop_front() {
+            vec.push(elem);
+        }
+        vec
+    }
+}
+
+// NOTE: Popping breaks both the prev and next links of every node, so no
+// Rc cycle is left behind to leak, and it's a loop so nothing recurses.
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
-        while self.pop_front().is_some() {}
+        self.clear();
+    }
+}
+
+// NOTE: A cursor sits on a node, or on the ghost past either end when
+// current is None. It keeps its own Rc to the node it's on, and the borrow of
+// the list stops anything unlinking nodes under it. current() hands out a
+// guard the same way the peeks do.
+pub struct Cursor<'a, T> {
+    list: &'a List<T>,
+    current: Link<T>,
+    index: Option<usize>,
+}
+
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    current: Link<T>,
+    index: Option<usize>,
+}
+
+// NOTE: Movement shared by both cursors. Off the ghost forward goes to the
+// head and back goes to the tail, stepping off either end lands on the ghost.
+fn step<T>(list: &List<T>, current: &mut Link<T>, index: &mut Option<usize>, forward: bool) {
+    match current.take() {
+        Some(node) => {
+            let node = node.borrow();
+            *current = if forward {
+                node.next.clone()
+            } else {
+                node.prev.clone()
+            };
+            *index = match (current.is_some(), forward) {
+                (false, _) => None,
+                (true, true) => index.map(|i| i + 1),
+                (true, false) => index.map(|i| i - 1),
+            };
+        }
+        None => {
+            *current = if forward {
+                list.head.clone()
+            } else {
+                list.tail.clone()
+            };
+            *index = match (current.is_some(), forward) {
+                (false, _) => None,
+                (true, true) => Some(0),
+                (true, false) => Some(list.len - 1),
+            };
+        }
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    fn new(list: &'a List<T>, current: Link<T>, index: usize) -> Self {
+        let index = current.as_ref().map(|_| index);
+        Cursor {
+            list,
+            current,
+            index,
+        }
+    }
+
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn current(&self) -> Option<Ref<'_, T>> {
+        self.current
+            .as_ref()
+            .map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    pub fn move_next(&mut self) {
+        step(self.list, &mut self.current, &mut self.index, true);
+    }
+
+    pub fn move_prev(&mut self) {
+        step(self.list, &mut self.current, &mut self.index, false);
+    }
+}
+
+impl<T> CursorMut<'_, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn current(&self) -> Option<Ref<'_, T>> {
+        self.current
+            .as_ref()
+            .map(|node| Ref::map(node.borrow(), |node| &node.elem))
+    }
+
+    pub fn current_mut(&mut self) -> Option<RefMut<'_, T>> {
+        self.current
+            .as_ref()
+            .map(|node| RefMut::map(node.borrow_mut(), |node| &mut node.elem))
+    }
+
+    pub fn move_next(&mut self) {
+        step(self.list, &mut self.current, &mut self.index, true);
+    }
+
+    pub fn move_prev(&mut self) {
+        step(self.list, &mut self.current, &mut self.index, false);
+    }
+
+    // NOTE: Unlinks the current node and moves onto the one after it (the
+    // ghost if it was the tail). On the ghost there's nothing to remove.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current.take()?;
+        let prev = node.borrow_mut().prev.take();
+        let next = node.borrow_mut().next.take();
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next.clone(),
+            None => self.list.head = next.clone(),
+        }
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.clone(),
+            None => {
+                self.list.tail = prev;
+                self.index = None;
+            }
+        }
+        self.current = next;
+        self.list.len -= 1;
+        // NOTE: Both links to it are gone, the cursor's Rc is the last one
+        Some(Rc::try_unwrap(node).ok().unwrap().into_inner().elem)
+    }
+
+    // NOTE: On the ghost "after" wraps round to the front of the list. The
+    // cursor stays where it is either way.
+    pub fn insert_after(&mut self, elem: T) {
+        let Some(node) = &self.current else {
+            self.list.push_front(elem);
+            return;
+        };
+        let new_node = Node::new(elem);
+        let next = node.borrow_mut().next.replace(new_node.clone());
+        match &next {
+            Some(next) => next.borrow_mut().prev = Some(new_node.clone()),
+            None => self.list.tail = Some(new_node.clone()),
+        }
+        let mut new = new_node.borrow_mut();
+        new.prev = Some(node.clone());
+        new.next = next;
+        self.list.len += 1;
+    }
+}
+
+pub struct Drain<'a, T>(&'a mut List<T>);
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.0.clear();
     }
 }
 
 pub struct IntoIter<T>(List<T>);
 
+impl<T> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
@@ -153,15 +662,92 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
+// NOTE: nth_back is left as the default, which calls next_back n + 1 times
+// and so pops and drops each skipped element. That walks the same nodes a
+// hand written one would, so there's nothing to gain by overriding it.
 impl<T> DoubleEndedIterator for IntoIter<T> {
     fn next_back(&mut self) -> Option<T> {
         self.0.pop_back()
     }
 }
 
+// NOTE: A Ref can only borrow from a RefCell that outlives it, and past the
+// head every node is only reachable through a guard on the one before. So
+// the iterators keep their own Rc of the next node, and each element comes
+// out as an Rc of its node to take the guard from. The 'a keeps the list
+// borrowed, so no node gets unlinked while any of these is around.
+pub struct Iter<'a, T> {
+    next: Link<T>,
+    _list: PhantomData<&'a List<T>>,
+}
+
+pub struct IterMut<'a, T> {
+    next: Link<T>,
+    _list: PhantomData<&'a List<T>>,
+}
+
+pub struct Elem<'a, T> {
+    node: Rc<RefCell<Node<T>>>,
+    _list: PhantomData<&'a List<T>>,
+}
+
+pub struct ElemMut<'a, T> {
+    node: Rc<RefCell<Node<T>>>,
+    _list: PhantomData<&'a List<T>>,
+}
+
+impl<T> Elem<'_, T> {
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref::map(self.node.borrow(), |node| &node.elem)
+    }
+}
+
+impl<T> ElemMut<'_, T> {
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        RefMut::map(self.node.borrow_mut(), |node| &mut node.elem)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = Elem<'a, T>;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = Elem<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.take().map(|node| {
+            self.next = node.borrow().next.clone();
+            Elem {
+                node,
+                _list: PhantomData,
+            }
+        })
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = ElemMut<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.take().map(|node| {
+            self.next = node.borrow().next.clone();
+            ElemMut {
+                node,
+                _list: PhantomData,
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::List;
+    use super::{List, TryPopError};
 
     #[test]
     fn test_basics() {
@@ -250,4 +836,778 @@ mod test {
         assert_eq!(iter.next_back(), None);
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_interleaved() {
+        let mut list = List::new();
+
+        // NOTE: 0 -> 1 -> 2 elements from both ends, then back down
+        list.push_front(2);
+        list.push_back(3);
+        list.push_front(1);
+        list.push_back(4);
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+
+        // NOTE: through empty over and over, from every combination of ends
+        for i in 0..10 {
+            list.push_back(i);
+            assert_eq!(list.pop_front(), Some(i));
+            list.push_front(i);
+            assert_eq!(list.pop_back(), Some(i));
+            list.push_front(i);
+            list.push_back(i + 1);
+            assert_eq!(list.pop_front(), Some(i));
+            assert_eq!(list.pop_front(), Some(i + 1));
+            assert!(list.peek_front().is_none());
+            assert!(list.peek_back().is_none());
+        }
+    }
+
+    #[test]
+    fn test_no_leaks() {
+        use std::rc::Rc;
+
+        // NOTE: every element holds a clone of probe, so once they're all
+        // freed the count is back to one
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..10 {
+            list.push_front(probe.clone());
+            list.push_back(probe.clone());
+        }
+        list.pop_front();
+        list.pop_back();
+        assert_eq!(Rc::strong_count(&probe), 19);
+
+        drop(list);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn test_peek_mut() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        assert_eq!(*list.peek_front().unwrap(), 1);
+        assert_eq!(*list.peek_back().unwrap(), 3);
+
+        *list.peek_front_mut().unwrap() = 10;
+        *list.peek_back_mut().unwrap() *= 10;
+
+        // NOTE: the writes went through to the nodes
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.pop_back(), Some(30));
+
+        // NOTE: with one element both ends peek the same node
+        *list.peek_back_mut().unwrap() += 5;
+        assert_eq!(*list.peek_front().unwrap(), 7);
+        assert_eq!(list.pop_front(), Some(7));
+        assert!(list.peek_front().is_none());
+        assert!(list.peek_back_mut().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_peek_alias_panics() {
+        let mut list = List::new();
+        list.push_front(1);
+
+        list.push_back(2);
+
+        // NOTE: both guards are for the head node
+        let guard = list.peek_front();
+        let _mut_guard = list.peek_front_mut();
+        drop(guard);
+    }
+
+    #[test]
+    fn test_into_iter_directions() {
+        let build = || {
+            let mut list = List::new();
+            for i in 1..=5 {
+                list.push_back(i);
+            }
+            list
+        };
+
+        assert_eq!(build().into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            build().into_iter().rev().collect::<Vec<_>>(),
+            vec![5, 4, 3, 2, 1]
+        );
+
+        // NOTE: both ends meet in the middle without handing anything out twice
+        let mut iter = build().into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next_back(), Some(5));
+        assert_eq!(iter.next_back(), Some(4));
+        assert_eq!(iter.next(), Some(2));
+        assert_eq!(iter.next(), Some(3));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_into_iter_early_drop() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..6 {
+            list.push_back(probe.clone());
+        }
+
+        let mut iter = list.into_iter();
+        drop(iter.next());
+        drop(iter.next_back());
+        assert_eq!(Rc::strong_count(&probe), 5);
+
+        // NOTE: dropping the iterator frees the four it never handed out
+        drop(iter);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn test_len_clear() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut list = List::new();
+        assert_eq!(list.len(), 0);
+        assert!(list.is_empty());
+
+        list.push_front(probe.clone());
+        list.push_back(probe.clone());
+        list.push_back(probe.clone());
+        assert_eq!(list.len(), 3);
+        list.pop_back();
+        assert_eq!(list.len(), 2);
+        list.pop_front();
+        assert_eq!(list.len(), 1);
+        list.push_front(probe.clone());
+        assert_eq!(list.len(), 2);
+        assert!(!list.is_empty());
+
+        // NOTE: clear frees the elements right away and leaves it reusable
+        list.clear();
+        assert_eq!(list.len(), 0);
+        assert_eq!(Rc::strong_count(&probe), 1);
+        list.push_back(probe.clone());
+        assert_eq!(list.len(), 1);
+        assert_eq!(
+            list.pop_front().map(|rc| Rc::ptr_eq(&rc, &probe)),
+            Some(true)
+        );
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn test_long_drop() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..1_000_000 {
+            list.push_back(probe.clone());
+        }
+        assert_eq!(list.len(), 1_000_000);
+
+        drop(list);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn test_iter_guards() {
+        let mut list = List::new();
+        for i in 1..=4 {
+            list.push_back(i);
+        }
+
+        let copies: Vec<i32> = list.iter().map(|x| *x.borrow()).collect();
+        assert_eq!(copies, vec![1, 2, 3, 4]);
+
+        // NOTE: two iterators at once, holding every guard from both
+        let outer: Vec<_> = list.iter().collect();
+        let inner: Vec<_> = list.iter().collect();
+        let outer_guards: Vec<_> = outer.iter().map(|x| x.borrow()).collect();
+        let inner_guards: Vec<_> = inner.iter().map(|x| x.borrow()).collect();
+        for (a, b) in outer_guards.iter().zip(inner_guards.iter()) {
+            assert_eq!(**a, **b);
+        }
+        drop(outer_guards);
+        drop(inner_guards);
+
+        for x in list.iter_mut() {
+            *x.borrow_mut() *= 10;
+        }
+        assert_eq!(
+            list.iter().map(|x| *x.borrow()).collect::<Vec<_>>(),
+            vec![10, 20, 30, 40]
+        );
+
+        let empty: List<i32> = List::new();
+        assert!(empty.iter().next().is_none());
+        assert!(empty.iter_mut().next().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_iter_mut_alias_panics() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+
+        let first = list.iter_mut().next().unwrap();
+        let _guard = first.borrow_mut();
+        // NOTE: the front is the element iter_mut's guard is already on
+        let _front = list.peek_front_mut();
+    }
+
+    #[test]
+    fn test_traits() {
+        let build = |n| {
+            let mut list = List::new();
+            for i in 1..=n {
+                list.push_back(i);
+            }
+            list
+        };
+
+        assert_eq!(format!("{:?}", build(3)), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+
+        assert_eq!(build(3), build(3));
+        assert_ne!(build(3), build(2));
+        assert_ne!(build(2), build(3));
+        let other = build(3);
+        *other.peek_back_mut().unwrap() = 4;
+        assert_ne!(build(3), other);
+        assert_eq!(List::<i32>::new(), List::new());
+
+        // NOTE: mutating either side leaves the other alone
+        let list = build(3);
+        let mut copy = list.clone();
+        assert_eq!(list, copy);
+        for x in copy.iter_mut() {
+            *x.borrow_mut() *= 10;
+        }
+        copy.push_front(0);
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", copy), "[0, 10, 20, 30]");
+        *list.peek_front_mut().unwrap() = 7;
+        assert_eq!(*copy.peek_back().unwrap(), 30);
+        assert_eq!(copy.pop_back(), Some(30));
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_debug_with_guard_alive() {
+        let mut list = List::new();
+        list.push_back(1);
+        list.push_back(2);
+
+        // NOTE: shared guards don't get in the way of Debug or eq
+        let front = list.peek_front().unwrap();
+        let back = list.peek_back().unwrap();
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+        assert_eq!(list, list.clone());
+        assert_eq!((*front, *back), (1, 2));
+    }
+
+    #[test]
+    fn test_traits_long() {
+        // NOTE: none of the three recurse down the chain
+        let mut list = List::new();
+        for i in 0..100_000 {
+            list.push_back(i);
+        }
+        let copy = list.clone();
+        assert_eq!(list, copy);
+        assert_eq!(format!("{:?}", copy).len(), format!("{:?}", list).len());
+    }
+
+    #[test]
+    fn test_vec_round_trip() {
+        let list = List::from(vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+        assert_eq!(*list.peek_front().unwrap(), 1);
+        assert_eq!(*list.peek_back().unwrap(), 4);
+        assert_eq!(list.to_vec(), vec![1, 2, 3, 4]);
+
+        let vec: Vec<_> = list.into();
+        assert_eq!(vec, vec![1, 2, 3, 4]);
+        assert_eq!(Vec::from(List::from(vec.clone())), vec);
+
+        let empty = List::from(Vec::<i32>::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.to_vec(), vec![]);
+        assert_eq!(Vec::from(empty), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_vec_drop_counts() {
+        use std::cell::Cell;
+        use std::panic::{self, AssertUnwindSafe};
+
+        struct Counted<'a>(&'a Cell<usize>);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        // NOTE: moving through the conversion drops nothing along the way
+        let drops = Cell::new(0);
+        let list = List::from((0..5).map(|_| Counted(&drops)).collect::<Vec<_>>());
+        assert_eq!(drops.get(), 0);
+        let vec = Vec::from(list);
+        assert_eq!(drops.get(), 0);
+        assert_eq!(vec.len(), 5);
+        drop(vec);
+        assert_eq!(drops.get(), 5);
+
+        // NOTE: a panic inside the conversion itself still drops every
+        // element once. An extra Rc of the third node makes its try_unwrap
+        // fail after two elements have already moved into the Vec: those go
+        // with the Vec, the last two with the list, and the third once the
+        // extra Rc does.
+        let drops = Cell::new(0);
+        let list = List::from((0..5).map(|_| Counted(&drops)).collect::<Vec<_>>());
+        let third = {
+            let first = list.head.as_ref().unwrap().borrow();
+            let second = first.next.as_ref().unwrap().borrow();
+            second.next.clone().unwrap()
+        };
+        let result = panic::catch_unwind(AssertUnwindSafe(|| Vec::from(list)));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 4);
+        drop(third);
+        assert_eq!(drops.get(), 5);
+    }
+
+    fn both_ways(list: List<i32>) -> (Vec<i32>, Vec<i32>) {
+        let forward = list.to_vec();
+        let backward = list.into_iter().rev().collect();
+        (forward, backward)
+    }
+
+    #[test]
+    fn test_append() {
+        let mut list = List::from(vec![1, 2, 3]);
+        let mut other = List::from(vec![4, 5]);
+        list.append(&mut other);
+        assert_eq!(list.len(), 5);
+        assert!(other.is_empty());
+        assert!(other.peek_front().is_none() && other.peek_back().is_none());
+        assert_eq!(both_ways(list), (vec![1, 2, 3, 4, 5], vec![5, 4, 3, 2, 1]));
+
+        // NOTE: other is still usable after being emptied
+        other.push_back(9);
+        assert_eq!(both_ways(other), (vec![9], vec![9]));
+
+        let mut list = List::from(vec![1, 2]);
+        list.append(&mut List::new());
+        assert_eq!(list.len(), 2);
+        assert_eq!(both_ways(list), (vec![1, 2], vec![2, 1]));
+
+        let mut list = List::new();
+        let mut other = List::from(vec![1, 2]);
+        list.append(&mut other);
+        assert_eq!((list.len(), other.len()), (2, 0));
+        assert_eq!(both_ways(list), (vec![1, 2], vec![2, 1]));
+
+        let mut list: List<i32> = List::new();
+        list.append(&mut List::new());
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list = List::from(vec![1, 2, 3, 4, 5]);
+        let back = list.split_off(2);
+        assert_eq!((list.len(), back.len()), (2, 3));
+        assert_eq!(both_ways(back), (vec![3, 4, 5], vec![5, 4, 3]));
+
+        // NOTE: the kept half is cleanly cut at its new tail
+        list.push_back(6);
+        assert_eq!(both_ways(list), (vec![1, 2, 6], vec![6, 2, 1]));
+
+        let mut list = List::from(vec![1, 2, 3]);
+        let back = list.split_off(0);
+        assert!(list.is_empty());
+        assert!(list.peek_back().is_none());
+        assert_eq!(back.len(), 3);
+        assert_eq!(both_ways(back), (vec![1, 2, 3], vec![3, 2, 1]));
+
+        let mut list = List::from(vec![1, 2, 3]);
+        let back = list.split_off(3);
+        assert!(back.is_empty());
+        assert_eq!(list.len(), 3);
+        assert_eq!(both_ways(list), (vec![1, 2, 3], vec![3, 2, 1]));
+
+        let mut list = List::from(vec![1, 2]);
+        let back = list.split_off(1);
+        assert_eq!(both_ways(list), (vec![1], vec![1]));
+        assert_eq!(both_ways(back), (vec![2], vec![2]));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_split_off_out_of_bounds() {
+        let mut list = List::from(vec![1, 2]);
+        list.split_off(3);
+    }
+
+    #[test]
+    fn test_split_append_no_leaks() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..6 {
+            list.push_back(probe.clone());
+        }
+        let mut back = list.split_off(4);
+        list.append(&mut back);
+        let rest = list.split_off(1);
+        drop((list, back, rest));
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn test_collect_extend() {
+        let mut list: List<i32> = (1..=4).collect();
+        assert_eq!(list.len(), 4);
+        assert_eq!(*list.peek_front().unwrap(), 1);
+        assert_eq!(*list.peek_back().unwrap(), 4);
+
+        list.extend(vec![5, 6]);
+        list.extend(std::iter::empty());
+        assert_eq!(list.len(), 6);
+        assert_eq!(*list.peek_back().unwrap(), 6);
+
+        let mut seen = Vec::new();
+        for x in &list {
+            seen.push(*x.borrow());
+        }
+        assert_eq!(seen, vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(
+            list.into_iter().rev().collect::<Vec<_>>(),
+            vec![6, 5, 4, 3, 2, 1]
+        );
+
+        let empty: List<i32> = std::iter::empty().collect();
+        assert!(empty.is_empty());
+        assert!(empty.peek_front().is_none() && empty.peek_back().is_none());
+    }
+
+    #[test]
+    fn test_cursor_walk() {
+        let list: List<i32> = (1..=3).collect();
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(*cursor.current().unwrap(), 1);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(2), Some(3))
+        );
+
+        // NOTE: off the back onto the ghost, then round to the front
+        cursor.move_next();
+        assert!(cursor.current().is_none());
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(0), Some(1))
+        );
+
+        // NOTE: and backwards off the front, round to the back
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(2), Some(3))
+        );
+        cursor.move_prev();
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(1), Some(2))
+        );
+
+        let cursor = list.cursor_back();
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(2), Some(3))
+        );
+
+        // NOTE: several cursors can share the list with peeks alive
+        let _front = list.peek_front();
+        let other = list.cursor_front();
+        assert_eq!(*other.current().unwrap(), 1);
+
+        let empty: List<i32> = List::new();
+        let mut cursor = empty.cursor_back();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert!(cursor.current().is_none());
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+    }
+
+    #[test]
+    fn test_cursor_mut_remove() {
+        let mut list: List<i32> = (1..=5).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(2), Some(4))
+        );
+        *cursor.current_mut().unwrap() *= 10;
+        drop(cursor);
+        assert_eq!(list.len(), 4);
+        assert_eq!(both_ways(list), (vec![1, 2, 40, 5], vec![5, 40, 2, 1]));
+
+        // NOTE: removing at both ends moves head and tail along
+        let mut list: List<i32> = (1..=3).collect();
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+        drop(cursor);
+        assert_eq!(list.len(), 1);
+        assert_eq!(*list.peek_front().unwrap(), 2);
+        assert_eq!(*list.peek_back().unwrap(), 2);
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(2));
+        drop(cursor);
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none() && list.peek_back().is_none());
+        list.push_back(7);
+        assert_eq!(both_ways(list), (vec![7], vec![7]));
+    }
+
+    #[test]
+    fn test_cursor_mut_insert() {
+        let mut list: List<i32> = (1..=3).collect();
+
+        // NOTE: inserting after the tail makes a new tail
+        let mut cursor = list.cursor_back_mut();
+        cursor.insert_after(4);
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(2), Some(3))
+        );
+        cursor.move_next();
+        assert_eq!(
+            (cursor.index(), cursor.current().map(|x| *x)),
+            (Some(3), Some(4))
+        );
+
+        // NOTE: and just before it
+        cursor.move_prev();
+        cursor.insert_after(35);
+        cursor.move_next();
+        cursor.move_next();
+        cursor.move_next();
+        cursor.insert_after(0);
+        drop(cursor);
+        assert_eq!(list.len(), 6);
+        assert_eq!(*list.peek_back().unwrap(), 4);
+        assert_eq!(
+            both_ways(list),
+            (vec![0, 1, 2, 3, 35, 4], vec![4, 35, 3, 2, 1, 0])
+        );
+
+        let mut list = List::new();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(1);
+        cursor.move_next();
+        cursor.insert_after(2);
+        drop(cursor);
+        assert_eq!(both_ways(list), (vec![1, 2], vec![2, 1]));
+    }
+
+    #[test]
+    fn test_cursor_no_leaks() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..4 {
+            list.push_back(probe.clone());
+        }
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        drop(cursor.remove_current());
+        cursor.insert_after(probe.clone());
+        cursor.move_prev();
+        cursor.insert_after(probe.clone());
+        drop(cursor);
+        assert_eq!(Rc::strong_count(&probe), 6);
+        drop(list);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list: List<i32> = (1..=6).collect();
+        list.retain(|&x| x != 1);
+        assert_eq!(
+            both_ways(list.clone()),
+            (vec![2, 3, 4, 5, 6], vec![6, 5, 4, 3, 2])
+        );
+        list.retain(|&x| x != 6);
+        assert_eq!(
+            both_ways(list.clone()),
+            (vec![2, 3, 4, 5], vec![5, 4, 3, 2])
+        );
+        list.retain(|&x| x == 2 || x == 5);
+        assert_eq!(list.len(), 2);
+        assert_eq!(both_ways(list.clone()), (vec![2, 5], vec![5, 2]));
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none() && list.peek_back().is_none());
+
+        // NOTE: the predicate sees every element once, in order
+        let mut list: List<i32> = (1..=4).collect();
+        let mut seen = Vec::new();
+        list.retain(|&x| {
+            seen.push(x);
+            x % 2 == 0
+        });
+        assert_eq!(seen, vec![1, 2, 3, 4]);
+        assert_eq!(both_ways(list), (vec![2, 4], vec![4, 2]));
+    }
+
+    #[test]
+    fn test_retain_frees_now() {
+        use std::rc::Rc;
+
+        let keep = Rc::new(());
+        let toss = Rc::new(());
+        let mut list = List::new();
+        for i in 0..6 {
+            list.push_back(if i % 3 == 0 {
+                keep.clone()
+            } else {
+                toss.clone()
+            });
+        }
+        list.retain(|rc| Rc::ptr_eq(rc, &keep));
+        assert_eq!(Rc::strong_count(&toss), 1);
+        assert_eq!(Rc::strong_count(&keep), 3);
+        assert_eq!(list.len(), 2);
+    }
+
+    #[test]
+    fn test_drain() {
+        use std::rc::Rc;
+
+        let mut list: List<i32> = (1..=4).collect();
+        assert_eq!(list.drain().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert!(list.is_empty());
+
+        let mut list: List<i32> = (1..=4).collect();
+        let mut drain = list.drain();
+        assert_eq!(drain.next(), Some(1));
+        assert_eq!(drain.next_back(), Some(4));
+        drop(drain);
+        assert!(list.is_empty());
+        assert!(list.peek_front().is_none() && list.peek_back().is_none());
+        list.push_back(5);
+        assert_eq!(both_ways(list), (vec![5], vec![5]));
+
+        // NOTE: the ones never handed out are freed when the Drain goes
+        let probe = Rc::new(());
+        let mut list = List::new();
+        for _ in 0..5 {
+            list.push_back(probe.clone());
+        }
+        let mut drain = list.drain();
+        let first = drain.next();
+        drop(drain);
+        assert_eq!(Rc::strong_count(&probe), 2);
+        drop(first);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+
+    #[test]
+    fn test_try_peek() {
+        let list: List<i32> = (1..=2).collect();
+
+        let mut front = list.peek_front_mut().unwrap();
+        assert!(list.try_peek_front().is_err());
+        assert!(list.try_peek_front_mut().is_err());
+        // NOTE: the other end is a different node so it's fine
+        assert_eq!(list.try_peek_back().ok().flatten().map(|x| *x), Some(2));
+        *front = 10;
+        drop(front);
+        assert_eq!(list.try_peek_front().ok().flatten().map(|x| *x), Some(10));
+
+        let back = list.peek_back().unwrap();
+        assert!(list.try_peek_back().is_ok());
+        assert!(list.try_peek_back_mut().is_err());
+        drop(back);
+        *list.try_peek_back_mut().unwrap().unwrap() = 20;
+        assert_eq!(list.to_vec(), vec![10, 20]);
+
+        let empty: List<i32> = List::new();
+        assert!(empty.try_peek_front().unwrap().is_none());
+        assert!(empty.try_peek_back_mut().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_try_pop() {
+        // NOTE: the Err paths can't be reached through the public API, which
+        // never hands out a node's Rc, so this reaches into list.head
+        let mut list: List<i32> = (1..=3).collect();
+
+        // NOTE: hold a guard on the head through our own Rc so the list
+        // itself isn't borrowed
+        let head = list.head.clone().unwrap();
+        let guard = head.borrow();
+        assert_eq!(list.try_pop_front(), Err(TryPopError::Borrowed));
+        assert_eq!(list.len(), 3);
+        // NOTE: the back end doesn't touch the head
+        assert_eq!(list.try_pop_back(), Ok(Some(3)));
+        drop(guard);
+        // NOTE: with no guard out our Rc still keeps the head shared
+        assert_eq!(list.try_pop_front(), Err(TryPopError::Shared));
+        assert_eq!(list.len(), 2);
+        drop(head);
+        assert_eq!(list.try_pop_front().ok(), Some(Some(1)));
+
+        // NOTE: popping the back relinks the node before it too
+        let mut list: List<i32> = (1..=3).collect();
+        let second = list.head.as_ref().unwrap().borrow().next.clone().unwrap();
+        let guard = second.borrow();
+        assert_eq!(list.try_pop_back(), Err(TryPopError::Borrowed));
+        assert_eq!(list.try_pop_front(), Err(TryPopError::Borrowed));
+        assert_eq!(both_ways(list.clone()), (vec![1, 2, 3], vec![3, 2, 1]));
+        drop(guard);
+        drop(second);
+        assert_eq!(list.try_pop_back().ok(), Some(Some(3)));
+        assert_eq!(list.try_pop_front().ok(), Some(Some(1)));
+        assert_eq!(list.try_pop_back().ok(), Some(Some(2)));
+        assert_eq!(list.try_pop_front().ok(), Some(None));
+    }
 }
@@ -1,10 +1,20 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
 // NOTE: first linked list
 pub mod first;
 // NOTE: second linked list
 pub mod second;
 // NOTE: third persistent stack
 pub mod third;
+// NOTE: third persistent stack, shareable across threads
+pub mod third_sync;
 // NOTE: fourth bad doubly linked list
 pub mod fourth;
 // NOTE: Ok unsafe singly linked list
 pub mod fifth;
+// NOTE: sixth production quality unsafe doubly linked deque
+pub mod sixth;
+// NOTE: singly linked stack with its nodes in a Vec, linked by index
+pub mod arena;
+// NOTE: fixed capacity stack stored inline, no heap
+pub mod stack;
@@ -1,39 +1,322 @@
 // NOTE: This is still a singly linked list
 // just more optimized than first.rs linked list.
 
-pub struct List<T> {
-    head: Link<T>,
+#[cfg(feature = "allocator_api")]
+use std::alloc::{Allocator, Global};
+#[cfg(not(feature = "allocator_api"))]
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::ops::{Range, RangeInclusive};
+
+// NOTE: allocator_api is nightly only, so on stable these stand in for it.
+// Global is the only Allocator and nodes go in a plain Box, which leaves
+// List<T> exactly the list it always was.
+#[cfg(not(feature = "allocator_api"))]
+pub use self::stable_alloc::{Allocator, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+mod stable_alloc {
+    // NOTE: Sealed so Global stays the only Allocator, nothing outside this
+    // module can name Sealed to implement it
+    mod sealed {
+        pub trait Sealed {}
+    }
+
+    #[doc(hidden)]
+    pub trait Allocator: sealed::Sealed {}
+
+    #[derive(Clone, Copy, Default, Debug)]
+    pub struct Global;
+
+    impl sealed::Sealed for Global {}
+    impl Allocator for Global {}
+}
+
+// NOTE: With the allocator_api feature every node is allocated through A,
+// e.g. a bump allocator, rather than always going to the global heap.
+pub struct List<T, A: Allocator = Global> {
+    head: Link<T, A>,
+    // NOTE: Kept up to date by everything that links or unlinks a node, so
+    // len() and the limit checks don't have to walk the list
+    len: usize,
+    // NOTE: Node allocations handed back by pop for push to reuse. They hold
+    // no live Node, which is why they're MaybeUninit. Only a list made with
+    // recycling() keeps any, up to pool_cap of them.
+    pool: Vec<Slot<T, A>>,
+    pool_cap: usize,
+    // NOTE: Most elements try_push will let the list hold, None for no limit
+    limit: Option<usize>,
+    alloc: A,
 }
 
-type Link<T> = Option<Box<Node<T>>>;
+#[cfg(feature = "allocator_api")]
+type NodeBox<T, A> = Box<Node<T, A>, A>;
+#[cfg(not(feature = "allocator_api"))]
+type NodeBox<T, A> = Box<Node<T, A>>;
 
-struct Node<T> {
+type Link<T, A> = Option<NodeBox<T, A>>;
+
+#[cfg(feature = "allocator_api")]
+type Slot<T, A> = Box<MaybeUninit<Node<T, A>>, A>;
+#[cfg(not(feature = "allocator_api"))]
+type Slot<T, A> = Box<MaybeUninit<Node<T, A>>>;
+
+struct Node<T, A: Allocator> {
     elem: T,
-    next: Link<T>,
+    next: Link<T, A>,
+    // NOTE: A is otherwise only used through next, which rustc won't accept
+    #[cfg(not(feature = "allocator_api"))]
+    _alloc: PhantomData<A>,
+}
+
+impl<T, A: Allocator> Node<T, A> {
+    fn new(elem: T, next: Link<T, A>) -> Self {
+        Node {
+            elem,
+            next,
+            #[cfg(not(feature = "allocator_api"))]
+            _alloc: PhantomData,
+        }
+    }
+}
+
+// NOTE: Every Box holds its own handle to the allocator, hence A: Clone.
+// Allocators are usually a reference or zero sized, so that's cheap.
+#[cfg(feature = "allocator_api")]
+fn boxed<T, A: Allocator + Clone>(node: Node<T, A>, alloc: &A) -> NodeBox<T, A> {
+    Box::new_in(node, alloc.clone())
+}
+
+#[cfg(not(feature = "allocator_api"))]
+fn boxed<T, A: Allocator>(node: Node<T, A>, _alloc: &A) -> NodeBox<T, A> {
+    Box::new(node)
+}
+
+// NOTE: Moves the Node out of its allocation and hands the allocation back
+// as an empty Slot for the pool
+#[cfg(feature = "allocator_api")]
+fn unbox<T, A: Allocator>(node: NodeBox<T, A>) -> (Node<T, A>, Slot<T, A>) {
+    let (raw, alloc) = Box::into_raw_with_allocator(node);
+    // SAFETY: same as the stable unbox below, the allocator that came out
+    // with raw goes back in with it
+    unsafe {
+        let node = raw.read();
+        (
+            node,
+            Box::from_raw_in(raw as *mut MaybeUninit<Node<T, A>>, alloc),
+        )
+    }
+}
+
+#[cfg(not(feature = "allocator_api"))]
+fn unbox<T, A: Allocator>(node: NodeBox<T, A>) -> (Node<T, A>, Slot<T, A>) {
+    let raw = Box::into_raw(node);
+    // SAFETY: raw came straight out of a live Box, so it points at an
+    // initialised Node. We read the Node out exactly once and from then on
+    // only treat the allocation as uninitialised memory, which
+    // MaybeUninit<Node<T, A>> has the same layout as.
+    unsafe {
+        let node = raw.read();
+        (node, Box::from_raw(raw as *mut MaybeUninit<Node<T, A>>))
+    }
 }
 
 // NOTE: No lifetimes here List has no associated lifetimes
 impl<T> List<T> {
-    pub fn new() -> Self {
-        List { head: None }
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    // NOTE: A list that keeps up to pool_cap popped node allocations around
+    // so a push/pop heavy workload stops hitting the allocator once warm.
+    pub fn recycling(pool_cap: usize) -> Self {
+        List {
+            head: None,
+            len: 0,
+            pool: Vec::with_capacity(pool_cap),
+            pool_cap,
+            limit: None,
+            alloc: Global,
+        }
+    }
+
+    // NOTE: A bounded list for producer/consumer style buffers. The limit is
+    // only enforced by try_push and push_evicting; push is still infallible
+    // and ignores it.
+    pub fn with_capacity_limit(max: usize) -> Self {
+        List {
+            head: None,
+            len: 0,
+            pool: Vec::new(),
+            pool_cap: 0,
+            limit: Some(max),
+            alloc: Global,
+        }
+    }
+
+    // NOTE: Both of the above. Fed with push_evicting it's a ring buffer that
+    // stops allocating once full, each push reusing the node just evicted.
+    pub fn recycling_with_capacity_limit(pool_cap: usize, max: usize) -> Self {
+        List {
+            head: None,
+            len: 0,
+            pool: Vec::with_capacity(pool_cap),
+            pool_cap,
+            limit: Some(max),
+            alloc: Global,
+        }
+    }
+
+    pub fn once(elem: T) -> Self {
+        let mut list = List::new();
+        list.push(elem);
+        list
+    }
+
+    pub fn repeat(elem: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        let mut list = List::new();
+        list.resize(n, elem);
+        list
+    }
+
+    // NOTE: Element i is f(i), so calls happen in index order
+    pub fn from_fn<F: FnMut(usize) -> T>(n: usize, mut f: F) -> Self {
+        let mut list = List::new();
+        for i in 0..n {
+            list.push(f(i));
+        }
+        list.reverse();
+        list
+    }
+}
+
+impl<T, A: Allocator + Clone> List<T, A> {
+    pub const fn new_in(alloc: A) -> Self {
+        Self::with_head(None, 0, alloc)
+    }
+
+    // NOTE: len has to be the number of nodes in head's chain
+    const fn with_head(head: Link<T, A>, len: usize, alloc: A) -> Self {
+        List {
+            head,
+            len,
+            pool: Vec::new(),
+            pool_cap: 0,
+            limit: None,
+            alloc,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // NOTE: Frees every pooled allocation, the cap stays as it was
+    pub fn shrink_pool(&mut self) {
+        self.pool = Vec::new();
     }
 
     pub fn push(&mut self, elem: T) {
-        let new_node = Box::new(Node {
-            elem,
-            next: self.head.take(),
-        });
+        let node = Node::new(elem, self.head.take());
+        self.head = Some(self.alloc_node(node));
+        self.len += 1;
+    }
+
+    // NOTE: Hands the element back if the list is already at its limit
+    pub fn try_push(&mut self, elem: T) -> Result<(), T> {
+        if let Some(limit) = self.limit {
+            if self.len >= limit {
+                return Err(elem);
+            }
+        }
+        self.push(elem);
+        Ok(())
+    }
 
-        self.head = Some(new_node)
+    // NOTE: Ring buffer flavour of try_push for lists made with
+    // with_capacity_limit. Instead of refusing, a full list drops its oldest
+    // element (the one at the far end) to make room and hands it back, so
+    // the list keeps the most recent `limit` pushes. The oldest node is
+    // limit links away from the head and there's no tail pointer, so every
+    // call walks the list and costs O(limit).
+    //
+    // Plain push and the other growing methods don't check the limit, so the
+    // list can already be longer than it. Then everything past the limit is
+    // unlinked, but only the oldest element comes back; the newer ones in
+    // between are dropped here.
+    pub fn push_evicting(&mut self, elem: T) -> Option<T> {
+        let Some(limit) = self.limit else {
+            self.push(elem);
+            return None;
+        };
+        if limit == 0 {
+            return Some(elem);
+        }
+        self.push(elem);
+        let mut cur_link = &mut self.head;
+        for _ in 0..limit {
+            cur_link = &mut cur_link.as_mut()?.next;
+        }
+        // NOTE: Unlinks everything past the limit front to back, so the last
+        // one to come off is the oldest
+        let mut rest = cur_link.take();
+        let mut oldest = None;
+        while let Some(node) = rest {
+            let node = self.reclaim(node);
+            rest = node.next;
+            self.len -= 1;
+            oldest = Some(node.elem);
+        }
+        oldest
     }
 
     pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
+            let node = self.reclaim(node);
             self.head = node.next;
+            self.len -= 1;
             node.elem
         })
     }
 
+    // NOTE: Every node the list makes comes from here, so a pooled
+    // allocation is always used up before the allocator is asked for one
+    fn alloc_node(&mut self, node: Node<T, A>) -> NodeBox<T, A> {
+        match self.pool.pop() {
+            Some(slot) => Box::write(slot, node),
+            None => boxed(node, &self.alloc),
+        }
+    }
+
+    // NOTE: Moves an unlinked node out of its Box, keeping the allocation in
+    // the pool if there's room for it. Every node the list frees goes
+    // through here.
+    fn reclaim(&mut self, node: NodeBox<T, A>) -> Node<T, A> {
+        if self.pool.len() < self.pool_cap {
+            let (node, slot) = unbox(node);
+            self.pool.push(slot);
+            node
+        } else {
+            *node
+        }
+    }
+
+    // NOTE: Frees a chain that's already been unlinked, dropping its
+    // elements. One node at a time, so it can't recurse like the chain's own
+    // drop would.
+    fn reclaim_all(&mut self, mut link: Link<T, A>) {
+        while let Some(node) = link {
+            link = self.reclaim(node).next;
+        }
+    }
+
     pub fn peek(&self) -> Option<&T> {
         self.head.as_ref().map(|node| &node.elem)
     }
@@ -42,9 +325,6 @@ impl<T> List<T> {
         self.head.as_mut().map(|node| &mut node.elem)
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter(self)
-    }
     // NOTE: We declare a fresh lifetime here for the *exact* borrow that
     // creates the iter. Now &self needs to be valid as long as the Iter is around.
     // NOTE: This is the same as the uncommented function with elision lifetimes
@@ -53,26 +333,707 @@ impl<T> List<T> {
     //         next: self.head.as_deref(),
     //     }
     // }
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T, A> {
         Iter {
             next: self.head.as_deref(),
         }
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, A> {
         IterMut {
             next: self.head.as_deref_mut(),
         }
     }
+
+    // NOTE: The leading run is just popped off the front, whatever is
+    // left over is already the list we want to hand back.
+    pub fn skip_while<F: FnMut(&T) -> bool>(mut self, mut pred: F) -> List<T, A> {
+        while let Some(elem) = self.peek() {
+            if !pred(elem) {
+                break;
+            }
+            self.pop();
+        }
+        self
+    }
+
+    pub fn take(mut self, n: usize) -> List<T, A> {
+        // NOTE: walk past the n nodes we keep, if we run out first we keep it all
+        let mut cur_link = &mut self.head;
+        for _ in 0..n {
+            match cur_link {
+                Some(node) => cur_link = &mut node.next,
+                None => return self,
+            }
+        }
+        let rest = cur_link.take();
+        self.reclaim_all(rest);
+        self.len = n;
+        self
+    }
+
+    pub fn skip(mut self, n: usize) -> List<T, A> {
+        for _ in 0..n {
+            if self.pop().is_none() {
+                break;
+            }
+        }
+        self
+    }
+
+    // NOTE: Ties keep the first occurrence, so we only move on a strict
+    // improvement. Iterator::max would hand back the last one instead.
+    pub fn min(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        let mut iter = self.iter();
+        let mut min = iter.next()?;
+        for elem in iter {
+            if elem < min {
+                min = elem;
+            }
+        }
+        Some(min)
+    }
+
+    pub fn max(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        let mut iter = self.iter();
+        let mut max = iter.next()?;
+        for elem in iter {
+            if elem > max {
+                max = elem;
+            }
+        }
+        Some(max)
+    }
+
+    // NOTE: Same tie rule as min/max, the first element with the best key wins.
+    // The best key is kept around so each element is only keyed once.
+    pub fn min_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut key: F) -> Option<&T> {
+        let mut iter = self.iter();
+        let mut min = iter.next()?;
+        let mut min_key = key(min);
+        for elem in iter {
+            let elem_key = key(elem);
+            if elem_key < min_key {
+                min = elem;
+                min_key = elem_key;
+            }
+        }
+        Some(min)
+    }
+
+    pub fn max_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut key: F) -> Option<&T> {
+        let mut iter = self.iter();
+        let mut max = iter.next()?;
+        let mut max_key = key(max);
+        for elem in iter {
+            let elem_key = key(elem);
+            if elem_key > max_key {
+                max = elem;
+                max_key = elem_key;
+            }
+        }
+        Some(max)
+    }
+
+    pub fn sum<S: std::iter::Sum<T>>(self) -> S {
+        self.into_iter().sum()
+    }
+
+    pub fn product<P: std::iter::Product<T>>(self) -> P {
+        self.into_iter().product()
+    }
+
+    pub fn count_matching<F: FnMut(&T) -> bool>(&self, mut pred: F) -> usize {
+        self.iter().filter(|elem| pred(elem)).count()
+    }
+
+    // NOTE: Each run is pushed up backwards and flipped once it ends, then the
+    // outer list gets the same treatment so both levels keep the original order.
+    pub fn group_by<F: FnMut(&T, &T) -> bool>(self, mut same: F) -> List<List<T, A>, A> {
+        let alloc = self.alloc.clone();
+        let mut groups = List::new_in(alloc.clone());
+        let mut group = List::new_in(alloc.clone());
+        for elem in self {
+            if let Some(prev) = group.peek() {
+                if !same(prev, &elem) {
+                    group.reverse();
+                    groups.push(std::mem::replace(&mut group, List::new_in(alloc.clone())));
+                }
+            }
+            group.push(elem);
+        }
+        if group.peek().is_some() {
+            group.reverse();
+            groups.push(group);
+        }
+        groups.reverse();
+        groups
+    }
+
+    // NOTE: Flips the links in place, no nodes are allocated or freed
+    pub(crate) fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut cur_link = self.head.take();
+        while let Some(mut node) = cur_link {
+            cur_link = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+
+    // NOTE: The set can only borrow elements while the list is left alone, so
+    // the first pass just records which ones are first occurrences and the
+    // second pass moves those out in order.
+    pub fn unique(self) -> List<T, A>
+    where
+        T: Eq + std::hash::Hash,
+    {
+        let keep: Vec<bool> = {
+            let mut seen = std::collections::HashSet::new();
+            self.iter().map(|elem| seen.insert(elem)).collect()
+        };
+
+        let mut unique = List::new_in(self.alloc.clone());
+        for (elem, keep) in self.into_iter().zip(keep) {
+            if keep {
+                unique.push(elem);
+            }
+        }
+        unique.reverse();
+        unique
+    }
+
+    // NOTE: Two pointers: lead starts k + 1 nodes ahead, and when it falls
+    // off the end the trailing pointer is sitting on the answer.
+    pub fn nth_from_end(&self, k: usize) -> Option<&T> {
+        let mut lead = self.iter();
+        for _ in 0..=k {
+            lead.next()?;
+        }
+        let mut trail = self.iter();
+        for _ in lead {
+            trail.next();
+        }
+        trail.next()
+    }
+
+    // NOTE: Tortoise and hare. The hare moves two nodes for every one the
+    // tortoise moves, so for even lengths this lands on the lower middle,
+    // e.g. the 2 in [1, 2, 3, 4].
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head.as_deref()?;
+        let mut fast = slow;
+        while let Some(next) = fast.next.as_deref().and_then(|node| node.next.as_deref()) {
+            fast = next;
+            slow = slow.next.as_deref()?;
+        }
+        Some(&slow.elem)
+    }
+
+    pub fn remove_first(&mut self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        // NOTE: walk the cursor up to the link that owns the match
+        let mut cur_link = &mut self.head;
+        while cur_link.as_ref().is_some_and(|node| node.elem != *value) {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        match cur_link.take() {
+            Some(mut node) => {
+                *cur_link = node.next.take();
+                self.reclaim(node);
+                self.len -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn remove_all(&mut self, value: &T) -> usize
+    where
+        T: PartialEq,
+    {
+        let mut removed = 0;
+        let mut removed_nodes = None;
+        let mut cur_link = &mut self.head;
+        while let Some(mut node) = cur_link.take() {
+            if node.elem == *value {
+                // NOTE: splice the next node into this link and look at it
+                // again, the match is kept aside and reclaimed after the walk
+                *cur_link = node.next.take();
+                node.next = removed_nodes;
+                removed_nodes = Some(node);
+                removed += 1;
+            } else {
+                cur_link = &mut cur_link.insert(node).next;
+            }
+        }
+        self.reclaim_all(removed_nodes);
+        self.len -= removed;
+        removed
+    }
+
+    // NOTE: The cheap swap for a singly linked list is with the head: the
+    // head's value moves into the removed slot and the head node is popped.
+    // So [1, 2, 3, 4].swap_remove(2) returns 3 and leaves [2, 1, 4].
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let mut head = self
+            .head
+            .take()
+            .unwrap_or_else(|| panic!("swap_remove index {} out of bounds", index));
+        self.head = head.next.take();
+        if index > 0 {
+            match self.iter_mut().nth(index - 1) {
+                Some(slot) => std::mem::swap(slot, &mut head.elem),
+                None => {
+                    // NOTE: put the head back so the list is intact after the panic
+                    head.next = self.head.take();
+                    self.head = Some(head);
+                    panic!("swap_remove index {} out of bounds", index);
+                }
+            }
+        }
+        self.len -= 1;
+        self.reclaim(head).elem
+    }
+
+    pub fn replace(&mut self, index: usize, elem: T) -> T {
+        match self.iter_mut().nth(index) {
+            Some(slot) => std::mem::replace(slot, elem),
+            None => panic!("replace index {} out of bounds", index),
+        }
+    }
+
+    pub fn pairs_mut(&mut self) -> PairsMut<'_, T, A> {
+        PairsMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+
+    // NOTE: Assumes the list is already sorted ascending. Equal elements go
+    // in after the ones already there. On an unsorted list it still inserts
+    // somewhere, but where is unspecified.
+    pub fn insert_sorted(&mut self, elem: T)
+    where
+        T: Ord,
+    {
+        let mut new_node = self.alloc_node(Node::new(elem, None));
+        let mut cur_link = &mut self.head;
+        while cur_link
+            .as_ref()
+            .is_some_and(|node| node.elem <= new_node.elem)
+        {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        new_node.next = cur_link.take();
+        *cur_link = Some(new_node);
+        self.len += 1;
+    }
+
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut iter = self.iter();
+        prefix.iter().all(|want| iter.next() == Some(want))
+    }
+
+    // NOTE: Same trick as nth_from_end: lead runs suffix.len() nodes ahead,
+    // so when it falls off the end trail is lined up with the suffix.
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut lead = self.iter();
+        for _ in 0..suffix.len() {
+            if lead.next().is_none() {
+                return false;
+            }
+        }
+        let mut trail = self.iter();
+        for _ in lead {
+            trail.next();
+        }
+        trail.eq(suffix.iter())
+    }
+
+    // NOTE: Works like slice::split: delimiters are dropped, back-to-back
+    // delimiters leave an empty segment, and there is always one more
+    // segment than delimiters (so the empty list gives one empty segment).
+    pub fn split_when<F: FnMut(&T) -> bool>(self, mut pred: F) -> List<List<T, A>, A> {
+        let alloc = self.alloc.clone();
+        let mut segments = List::new_in(alloc.clone());
+        let mut segment = List::new_in(alloc.clone());
+        for elem in self {
+            if pred(&elem) {
+                segment.reverse();
+                segments.push(std::mem::replace(&mut segment, List::new_in(alloc.clone())));
+            } else {
+                segment.push(elem);
+            }
+        }
+        segment.reverse();
+        segments.push(segment);
+        segments.reverse();
+        segments
+    }
+
+    // NOTE: Reverses the nodes in [start, end) by relinking them, nothing is
+    // moved or cloned. Bounds are checked up front so a bad range panics
+    // before any links are touched.
+    pub fn reverse_range(&mut self, start: usize, end: usize) {
+        assert!(start <= end, "reverse_range start {} > end {}", start, end);
+        assert!(end <= self.len, "reverse_range end {} out of bounds", end);
+
+        let mut cur_link = &mut self.head;
+        for _ in 0..start {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+
+        // NOTE: pop the range off the front of the rest, pushing each node
+        // onto reversed, so the first one popped ends up last
+        let mut rest = cur_link.take();
+        let mut reversed = None;
+        for _ in start..end {
+            let mut node = rest.unwrap();
+            rest = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+
+        // NOTE: hang the reversed run back on, then the rest after its end
+        *cur_link = reversed;
+        for _ in start..end {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        *cur_link = rest;
+    }
+
+    // NOTE: Move-to-front: the node at index is unlinked and pushed back on
+    // as the head, everything else keeps its relative order.
+    pub fn rotate_to_front(&mut self, index: usize) {
+        let mut cur_link = &mut self.head;
+        for _ in 0..index {
+            match cur_link {
+                Some(node) => cur_link = &mut node.next,
+                None => break,
+            }
+        }
+        let mut node = cur_link
+            .take()
+            .unwrap_or_else(|| panic!("rotate_to_front index {} out of bounds", index));
+        *cur_link = node.next.take();
+        node.next = self.head.take();
+        self.head = Some(node);
+    }
+
+    // NOTE: Cuts the list down to len nodes and frees the rest. Does nothing
+    // if the list is already short enough.
+    pub fn truncate(&mut self, len: usize) {
+        let mut cur_link = &mut self.head;
+        for _ in 0..len {
+            match cur_link {
+                Some(node) => cur_link = &mut node.next,
+                None => return,
+            }
+        }
+        let rest = cur_link.take();
+        self.reclaim_all(rest);
+        self.len = len;
+    }
+
+    pub fn resize(&mut self, new_len: usize, value: T)
+    where
+        T: Clone,
+    {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+
+        // NOTE: the clones are all alike, so their order in the chain doesn't
+        // matter and it can be built up from the front
+        let mut grown = None;
+        for _ in self.len..new_len {
+            grown = Some(self.alloc_node(Node::new(value.clone(), grown)));
+        }
+        let mut cur_link = &mut self.head;
+        for _ in 0..self.len {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        *cur_link = grown;
+        self.len = new_len;
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for elem in self.iter_mut() {
+            elem.clone_from(&value);
+        }
+    }
+
+    // NOTE: Same as into_iter().for_each(f), front to back
+    pub fn for_each<F: FnMut(T)>(self, f: F) {
+        self.into_iter().for_each(f);
+    }
+
+    // NOTE: Stops at the first Err and hands it straight back, the rest of
+    // the list is never looked at.
+    pub fn try_fold<B, E, F: FnMut(B, &T) -> Result<B, E>>(
+        &self,
+        init: B,
+        mut f: F,
+    ) -> Result<B, E> {
+        let mut acc = init;
+        for elem in self.iter() {
+            acc = f(acc, elem)?;
+        }
+        Ok(acc)
+    }
+
+    // NOTE: Has to start from the last element but can only walk from the
+    // head, so the references are collected into a Vec and folded backwards
+    // rather than recursing down the chain.
+    pub fn fold_right<B, F: FnMut(&T, B) -> B>(&self, init: B, mut f: F) -> B {
+        let elems: Vec<&T> = self.iter().collect();
+        elems.into_iter().rev().fold(init, |acc, elem| f(elem, acc))
+    }
+
+    // NOTE: Left to right, the head is the first accumulator
+    pub fn reduce<F: FnMut(T, T) -> T>(self, f: F) -> Option<T> {
+        self.into_iter().reduce(f)
+    }
+
+    // NOTE: Like Iterator::scan, the first None from f ends the new list and
+    // whatever is left of this one is dropped
+    pub fn scan<S, U, F: FnMut(&mut S, T) -> Option<U>>(
+        self,
+        mut state: S,
+        mut f: F,
+    ) -> List<U, A> {
+        let mut scanned = List::new_in(self.alloc.clone());
+        for elem in self {
+            match f(&mut state, elem) {
+                Some(out) => scanned.push(out),
+                None => break,
+            }
+        }
+        scanned.reverse();
+        scanned
+    }
+
+    // NOTE: Hands the list straight back so it can sit in the middle of a
+    // chain of consuming calls
+    pub fn inspect<F: FnMut(&T)>(self, mut f: F) -> List<T, A> {
+        for elem in self.iter() {
+            f(elem);
+        }
+        self
+    }
+
+    // NOTE: Alternates self, other, self, ... and once either runs out the
+    // rest of the other one goes on the end as it is
+    pub fn interleave(self, other: List<T, A>) -> List<T, A> {
+        let mut merged = List::new_in(self.alloc.clone());
+        let mut turn = self.into_iter();
+        let mut next = other.into_iter();
+        while let Some(elem) = turn.next() {
+            merged.push(elem);
+            std::mem::swap(&mut turn, &mut next);
+        }
+        for elem in next {
+            merged.push(elem);
+        }
+        merged.reverse();
+        merged
+    }
+
+    // NOTE: Pairs up by position and stops with the shorter list, the
+    // longer one's leftovers are dropped
+    pub fn zip_with<U, V, F: FnMut(T, U) -> V>(self, other: List<U, A>, mut f: F) -> List<V, A> {
+        let mut zipped = List::new_in(self.alloc.clone());
+        for (a, b) in self.into_iter().zip(other) {
+            zipped.push(f(a, b));
+        }
+        zipped.reverse();
+        zipped
+    }
+
+    // NOTE: Borrows both, all of self then all of other
+    pub fn chain<'a>(&'a self, other: &'a List<T, A>) -> Chain<'a, T, A> {
+        Chain {
+            first: self.iter(),
+            second: other.iter(),
+        }
+    }
+
+    // NOTE: Only between elements, never at the ends. The existing nodes
+    // are kept and a separator node is linked in after each but the last.
+    pub fn intersperse(mut self, sep: T) -> List<T, A>
+    where
+        T: Clone,
+    {
+        // NOTE: make every separator node up front, the walk below only
+        // relinks them
+        let count = self.len.saturating_sub(1);
+        let mut seps = None;
+        for _ in 0..count {
+            seps = Some(self.alloc_node(Node::new(sep.clone(), seps)));
+        }
+
+        let mut cur_link = &mut self.head;
+        while let Some(node) = cur_link {
+            let Some(next) = node.next.take() else {
+                break;
+            };
+            let mut sep_node = seps.take().unwrap();
+            seps = sep_node.next.replace(next);
+            cur_link = &mut node.next.insert(sep_node).next;
+        }
+        self.len += count;
+        self
+    }
+
+    // NOTE: Swaps the nodes in [start, end) for the replacement elements,
+    // like Vec::splice without handing back the removed ones. Panics if
+    // start > end or end is past the end of the list, before touching it.
+    pub fn splice<I: IntoIterator<Item = T>>(&mut self, start: usize, end: usize, replacement: I) {
+        assert!(start <= end, "splice start {} is after end {}", start, end);
+        let len = self.len;
+        assert!(
+            end <= len,
+            "splice end {} out of bounds for length {}",
+            end,
+            len
+        );
+
+        let mut cur_link = &mut self.head;
+        for _ in 0..start {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        let mut removed = cur_link.take();
+        let mut end_link = &mut removed;
+        for _ in start..end {
+            end_link = &mut end_link.as_mut().unwrap().next;
+        }
+        let rest = end_link.take();
+        self.reclaim_all(removed);
+        self.len -= end - start;
+
+        // NOTE: The removed nodes went back to the pool first, so the
+        // replacement can reuse them. It's built as its own chain with the
+        // rest on its end, then hung back on at start.
+        let mut chain = None;
+        let mut chain_end = &mut chain;
+        for elem in replacement {
+            let node = self.alloc_node(Node::new(elem, None));
+            chain_end = &mut chain_end.insert(node).next;
+            self.len += 1;
+        }
+        *chain_end = rest;
+
+        let mut cur_link = &mut self.head;
+        for _ in 0..start {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        *cur_link = chain;
+    }
+
+    // NOTE: Unlinks [start, end) and returns it as its own list, in order.
+    // Same bounds as splice, checked before anything is touched.
+    pub fn remove_range(&mut self, start: usize, end: usize) -> List<T, A> {
+        assert!(
+            start <= end,
+            "remove_range start {} is after end {}",
+            start,
+            end
+        );
+        let len = self.len;
+        assert!(
+            end <= len,
+            "remove_range end {} out of bounds for length {}",
+            end,
+            len
+        );
+
+        let mut cur_link = &mut self.head;
+        for _ in 0..start {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        let mut removed = cur_link.take();
+        let mut end_link = &mut removed;
+        for _ in start..end {
+            end_link = &mut end_link.as_mut().unwrap().next;
+        }
+        *cur_link = end_link.take();
+        self.len -= end - start;
+        List::with_head(removed, end - start, self.alloc.clone())
+    }
+
+    // NOTE: The rest keeps its nodes as they are, nothing is copied
+    pub fn split_first(mut self) -> Option<(T, List<T, A>)> {
+        let first = self.pop()?;
+        Some((first, self))
+    }
+
+    // NOTE: O(n), the node before the last can only be found by walking
+    pub fn split_last(mut self) -> Option<(T, List<T, A>)> {
+        let mut cur_link = &mut self.head;
+        while cur_link.as_ref()?.next.is_some() {
+            cur_link = &mut cur_link.as_mut().unwrap().next;
+        }
+        let last = cur_link.take()?;
+        self.len -= 1;
+        Some((self.reclaim(last).elem, self))
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// NOTE: The range reads front to back, so 0..3 gives 0 -> 1 -> 2
+impl From<Range<usize>> for List<usize> {
+    fn from(range: Range<usize>) -> Self {
+        let mut list = List::new();
+        for i in range {
+            list.push(i);
+        }
+        list.reverse();
+        list
+    }
+}
+
+impl From<RangeInclusive<usize>> for List<usize> {
+    fn from(range: RangeInclusive<usize>) -> Self {
+        let mut list = List::new();
+        for i in range {
+            list.push(i);
+        }
+        list.reverse();
+        list
+    }
 }
 
 // NOTE: Iter is generic over *some* lifetime, it does not care
-pub struct Iter<'a, T> {
-    next: Option<&'a Node<T>>,
+pub struct Iter<'a, T, A: Allocator = Global> {
+    next: Option<&'a Node<T, A>>,
 }
 
 // NOTE: We *do* have a lifetime here, because Iter has one that we need to define
-impl<'a, T> Iterator for Iter<'a, T> {
+impl<'a, T, A: Allocator> Iterator for Iter<'a, T, A> {
     // NOTE: We need one here as well, this is a type declaraction
     type Item = &'a T;
 
@@ -86,11 +1047,42 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
-pub struct IterMut<'a, T> {
-    next: Option<&'a mut Node<T>>,
+// NOTE: Overlapping pairs can't be a real Iterator: the second half of one
+// pair is the first half of the next, and an Iterator lets callers hold
+// both pairs at once. So next() borrows the PairsMut itself and a pair has
+// to be let go of before asking for the next one. Use it with `while let`.
+pub struct PairsMut<'a, T, A: Allocator = Global> {
+    next: Option<&'a mut Node<T, A>>,
+}
+
+impl<T, A: Allocator> PairsMut<'_, T, A> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<(&mut T, &mut T)> {
+        // NOTE: split the node so its elem and next are borrowed separately
+        let Node { elem, next, .. } = self.next.take()?;
+        let second = self.next.insert(next.as_deref_mut()?);
+        Some((elem, &mut second.elem))
+    }
+}
+
+pub struct IterMut<'a, T, A: Allocator = Global> {
+    next: Option<&'a mut Node<T, A>>,
+}
+
+pub struct Chain<'a, T, A: Allocator = Global> {
+    first: Iter<'a, T, A>,
+    second: Iter<'a, T, A>,
+}
+
+impl<'a, T, A: Allocator> Iterator for Chain<'a, T, A> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
 }
 
-impl<'a, T> Iterator for IterMut<'a, T> {
+impl<'a, T, A: Allocator> Iterator for IterMut<'a, T, A> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -103,9 +1095,18 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 
 // NOTE: Tuple stucts are an alternative form of struct,
 // useful for trivial wrappers around other types.
-pub struct IntoIter<T>(List<T>);
+pub struct IntoIter<T, A: Allocator = Global>(List<T, A>);
+
+impl<T, A: Allocator + Clone> IntoIterator for List<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
 
-impl<T> Iterator for IntoIter<T> {
+impl<T, A: Allocator + Clone> Iterator for IntoIter<T, A> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
         // NOTE: access to fields of a tuple struct numerically
@@ -113,7 +1114,7 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> Drop for List<T> {
+impl<T, A: Allocator> Drop for List<T, A> {
     fn drop(&mut self) {
         let mut cur_link = self.head.take();
         // NOTE: while let == do this thing until the pattern no longer matches
@@ -131,7 +1132,16 @@ impl<T> Drop for List<T> {
 mod test {
     use super::List;
 
-    #[test]
+    // NOTE: builds a list that reads front-to-back in slice order
+    fn from_slice<T: Clone>(items: &[T]) -> List<T> {
+        let mut list = List::new();
+        for item in items.iter().rev() {
+            list.push(item.clone());
+        }
+        list
+    }
+
+    #[test]
     fn basics() {
         // NOTE: Create empty list
         let mut list = List::new();
@@ -163,6 +1173,7 @@ mod test {
 
     // NOTE: Test peek functionality
     #[test]
+    #[allow(clippy::option_map_unit_fn)]
     fn test_peek() {
         let mut list = List::new();
 
@@ -219,4 +1230,997 @@ mod test {
         assert_eq!(iter.next(), Some(&mut 2));
         assert_eq!(iter.next(), Some(&mut 1));
     }
+
+    #[test]
+    fn test_skip_while() {
+        // NOTE: pushing 3, 2, 1 gives a list that reads [1, 2, 3]
+        let mut list = List::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+
+        // NOTE: everything skipped
+        let list = list.skip_while(|_| true);
+        assert_eq!(list.peek(), None);
+
+        // NOTE: some skipped
+        let mut list = List::new();
+        list.push(3);
+        list.push(2);
+        list.push(1);
+        let list = list.skip_while(|x| *x < 2);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![2, 3]);
+
+        // NOTE: nothing skipped, a later match doesn't count as leading
+        let mut list = List::new();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let list = list.skip_while(|x| *x < 3);
+        assert_eq!(list.into_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_take_skip() {
+        let build = || {
+            let mut list = List::new();
+            list.push(3);
+            list.push(2);
+            list.push(1);
+            list
+        };
+
+        assert_eq!(build().take(0).into_iter().collect::<Vec<_>>(), vec![]);
+        assert_eq!(build().take(2).into_iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            build().take(3).into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            build().take(10).into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        assert_eq!(
+            build().skip(0).into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(build().skip(2).into_iter().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(build().skip(3).into_iter().collect::<Vec<_>>(), vec![]);
+        assert_eq!(build().skip(10).into_iter().collect::<Vec<_>>(), vec![]);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let list = from_slice(&[3, 1, 4, 1, 5, 9, 2, 9]);
+        assert_eq!(list.min(), Some(&1));
+        assert_eq!(list.max(), Some(&9));
+
+        // NOTE: ties hand back the first occurrence
+        assert!(std::ptr::eq(
+            list.min().unwrap(),
+            list.iter().nth(1).unwrap()
+        ));
+        assert!(std::ptr::eq(
+            list.max().unwrap(),
+            list.iter().nth(5).unwrap()
+        ));
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.max(), None);
+    }
+
+    #[test]
+    fn test_min_max_by_key() {
+        let list = from_slice(&[("a", 3), ("b", 1), ("c", 7), ("d", 1), ("e", 7)]);
+
+        // NOTE: ties on the key hand back the first occurrence
+        assert_eq!(list.min_by_key(|pair| pair.1), Some(&("b", 1)));
+        assert_eq!(list.max_by_key(|pair| pair.1), Some(&("c", 7)));
+
+        let empty: List<(&str, i32)> = List::new();
+        assert_eq!(empty.min_by_key(|pair| pair.1), None);
+        assert_eq!(empty.max_by_key(|pair| pair.1), None);
+    }
+
+    #[test]
+    fn test_sum_product() {
+        let list = from_slice(&[1, 2, 3, 4]);
+        assert_eq!(list.sum::<i32>(), 10);
+
+        let list = from_slice(&[1, 2, 3, 4]);
+        assert_eq!(list.product::<i32>(), 24);
+
+        // NOTE: the empty list folds to the identity
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.sum::<i32>(), 0);
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.product::<i32>(), 1);
+    }
+
+    #[test]
+    fn test_count_matching() {
+        let list = from_slice(&(1..=10).collect::<Vec<_>>());
+        assert_eq!(list.count_matching(|x| x % 2 == 0), 5);
+        assert_eq!(list.count_matching(|x| *x > 100), 0);
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.count_matching(|_| true), 0);
+    }
+
+    #[test]
+    fn test_group_by() {
+        let list = from_slice(&[1, 1, 2, 3, 3, 3]);
+        let groups: Vec<Vec<i32>> = list
+            .group_by(|a, b| a == b)
+            .into_iter()
+            .map(|group| group.into_iter().collect())
+            .collect();
+        assert_eq!(groups, vec![vec![1, 1], vec![2], vec![3, 3, 3]]);
+
+        let empty: List<i32> = List::new();
+        assert!(empty.group_by(|a, b| a == b).peek().is_none());
+    }
+
+    #[test]
+    fn test_unique() {
+        let list = from_slice(&[1, 2, 1, 3, 2]);
+        assert_eq!(list.unique().into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let list = from_slice(&["b", "a", "b", "b"]);
+        assert_eq!(
+            list.unique().into_iter().collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+    }
+
+    #[test]
+    fn test_nth_from_end() {
+        let list = from_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(list.nth_from_end(0), Some(&5));
+        assert_eq!(list.nth_from_end(2), Some(&3));
+        assert_eq!(list.nth_from_end(4), Some(&1));
+        assert_eq!(list.nth_from_end(5), None);
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.nth_from_end(0), None);
+    }
+
+    #[test]
+    fn test_middle() {
+        assert_eq!(from_slice(&[1, 2, 3, 4, 5]).middle(), Some(&3));
+        // NOTE: even lengths give the lower middle
+        assert_eq!(from_slice(&[1, 2, 3, 4]).middle(), Some(&2));
+        assert_eq!(from_slice(&[1, 2]).middle(), Some(&1));
+        assert_eq!(from_slice(&[1]).middle(), Some(&1));
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.middle(), None);
+    }
+
+    #[test]
+    fn test_remove_first() {
+        let mut list = from_slice(&[1, 2, 3, 2, 4]);
+
+        // NOTE: head
+        assert!(list.remove_first(&1));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 2, 4]);
+
+        // NOTE: middle, only the first of the two 2s goes
+        assert!(list.remove_first(&3));
+        assert!(list.remove_first(&2));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+
+        // NOTE: tail
+        assert!(list.remove_first(&4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2]);
+
+        // NOTE: not present
+        assert!(!list.remove_first(&7));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut list = from_slice(&[2, 1, 2, 2, 3, 2, 4, 2]);
+        assert_eq!(list.remove_all(&2), 5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3, 4]);
+
+        assert_eq!(list.remove_all(&7), 0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3, 4]);
+
+        let mut list = from_slice(&[5, 5, 5]);
+        assert_eq!(list.remove_all(&5), 3);
+        assert_eq!(list.peek(), None);
+    }
+
+    #[test]
+    fn test_swap_remove() {
+        let mut list = from_slice(&[1, 2, 3, 4]);
+        assert_eq!(list.swap_remove(2), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 1, 4]);
+
+        // NOTE: the last index pulls the head all the way to the back
+        assert_eq!(list.swap_remove(2), 4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        // NOTE: index 0 is just a pop
+        assert_eq!(list.swap_remove(0), 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_swap_remove_out_of_bounds() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.swap_remove(3);
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut list = from_slice(&[1, 2, 3]);
+        assert_eq!(list.replace(0, 10), 1);
+        assert_eq!(list.replace(1, 20), 2);
+        assert_eq!(list.replace(2, 30), 3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_replace_out_of_bounds() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.replace(3, 0);
+    }
+
+    #[test]
+    fn test_pairs_mut() {
+        // NOTE: smooth each element towards its left neighbour
+        let mut list = from_slice(&[2, 4, 8]);
+        let mut pairs = list.pairs_mut();
+        while let Some((left, right)) = pairs.next() {
+            *right = (*left + *right) / 2;
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 5]);
+
+        // NOTE: both halves of a pair can be written
+        let mut list = from_slice(&[1, 2, 3]);
+        let mut pairs = list.pairs_mut();
+        let mut seen = Vec::new();
+        while let Some((left, right)) = pairs.next() {
+            seen.push((*left, *right));
+            std::mem::swap(left, right);
+        }
+        // NOTE: the 1 gets swapped along each pair until it reaches the back
+        assert_eq!(seen, vec![(1, 2), (1, 3)]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 1]);
+
+        // NOTE: fewer than two elements means no pairs
+        let mut list = from_slice(&[1]);
+        assert!(list.pairs_mut().next().is_none());
+        let mut list: List<i32> = List::new();
+        assert!(list.pairs_mut().next().is_none());
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        let mut list = List::new();
+        for elem in [5, 1, 4, 1, 9, 0, 5, 3] {
+            list.insert_sorted(elem);
+            let items: Vec<_> = list.iter().copied().collect();
+            assert!(items.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 1, 3, 4, 5, 5, 9]
+        );
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let list = from_slice(&["GET", "/", "HTTP/1.1"]);
+        assert!(list.starts_with(&["GET", "/"]));
+        assert!(list.starts_with(&["GET", "/", "HTTP/1.1"]));
+        assert!(!list.starts_with(&["POST"]));
+        assert!(!list.starts_with(&["GET", "/index"]));
+        assert!(list.starts_with(&[]));
+        assert!(!list.starts_with(&["GET", "/", "HTTP/1.1", "extra"]));
+
+        let empty: List<i32> = List::new();
+        assert!(empty.starts_with(&[]));
+        assert!(!empty.starts_with(&[1]));
+    }
+
+    #[test]
+    fn test_ends_with() {
+        let list = from_slice(&["GET", "/", "HTTP/1.1"]);
+        assert!(list.ends_with(&["/", "HTTP/1.1"]));
+        assert!(list.ends_with(&["GET", "/", "HTTP/1.1"]));
+        assert!(!list.ends_with(&["HTTP/2"]));
+        assert!(!list.ends_with(&["GET", "HTTP/1.1"]));
+        assert!(list.ends_with(&[]));
+        assert!(!list.ends_with(&["extra", "GET", "/", "HTTP/1.1"]));
+
+        let empty: List<i32> = List::new();
+        assert!(empty.ends_with(&[]));
+        assert!(!empty.ends_with(&[1]));
+    }
+
+    #[test]
+    fn test_split_when() {
+        fn segments(list: List<List<i32>>) -> Vec<Vec<i32>> {
+            list.into_iter()
+                .map(|segment| segment.into_iter().collect())
+                .collect()
+        }
+
+        let list = from_slice(&[1, 0, 2, 3, 0, 0, 4]);
+        assert_eq!(
+            segments(list.split_when(|x| *x == 0)),
+            vec![vec![1], vec![2, 3], vec![], vec![4]]
+        );
+
+        // NOTE: delimiters at the ends leave empty segments there
+        let list = from_slice(&[0, 1, 0]);
+        assert_eq!(
+            segments(list.split_when(|x| *x == 0)),
+            vec![vec![], vec![1], vec![]]
+        );
+
+        let list = from_slice(&[1, 2]);
+        assert_eq!(segments(list.split_when(|x| *x == 0)), vec![vec![1, 2]]);
+    }
+
+    #[test]
+    fn test_reverse_range() {
+        let mut list = from_slice(&[1, 2, 3, 4, 5, 6]);
+        list.reverse_range(1, 4);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 4, 3, 2, 5, 6]
+        );
+
+        // NOTE: touching the head
+        let mut list = from_slice(&[1, 2, 3, 4, 5, 6]);
+        list.reverse_range(0, 3);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![3, 2, 1, 4, 5, 6]
+        );
+
+        // NOTE: touching the tail, and the whole list
+        list.reverse_range(3, 6);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![3, 2, 1, 6, 5, 4]
+        );
+        list.reverse_range(0, 6);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![4, 5, 6, 1, 2, 3]
+        );
+
+        // NOTE: empty ranges are a no-op, even at the very end
+        list.reverse_range(2, 2);
+        list.reverse_range(6, 6);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![4, 5, 6, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_reverse_range_out_of_bounds() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.reverse_range(1, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "reverse_range start 2 > end 1")]
+    fn test_reverse_range_backwards() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.reverse_range(2, 1);
+    }
+
+    #[test]
+    fn test_rotate_to_front() {
+        let mut list = from_slice(&[1, 2, 3, 4]);
+        list.rotate_to_front(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 1, 2, 4]);
+
+        // NOTE: the tail
+        list.rotate_to_front(3);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 1, 2]);
+
+        // NOTE: the head stays put
+        list.rotate_to_front(0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 3, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_rotate_to_front_out_of_bounds() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.rotate_to_front(3);
+    }
+
+    #[test]
+    fn test_truncate_resize() {
+        let mut list = from_slice(&[1, 2]);
+        list.resize(5, 0);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 0, 0, 0]
+        );
+
+        list.resize(2, 9);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        list.resize(2, 9);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        list.resize(0, 9);
+        assert_eq!(list.peek(), None);
+
+        let mut list = from_slice(&[1, 2, 3]);
+        list.truncate(5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        list.truncate(1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1]);
+        list.truncate(0);
+        assert_eq!(list.peek(), None);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.fill(0);
+        assert_eq!(list.iter().count(), 3);
+        assert!(list.iter().all(|x| *x == 0));
+
+        let mut empty: List<i32> = List::new();
+        empty.fill(0);
+        assert_eq!(empty.peek(), None);
+    }
+
+    #[test]
+    fn test_const_new() {
+        const EMPTY: List<i32> = List::new();
+        static ALSO_EMPTY: List<i32> = List::new();
+
+        // NOTE: every use of a const is a fresh list
+        let mut list = EMPTY;
+        list.push(1);
+        assert_eq!(list.peek(), Some(&1));
+        assert_eq!(EMPTY.peek(), None);
+        assert_eq!(ALSO_EMPTY.peek(), None);
+    }
+
+    #[test]
+    fn test_try_push() {
+        let mut list = List::with_capacity_limit(3);
+        assert_eq!(list.try_push(1), Ok(()));
+        assert_eq!(list.try_push(2), Ok(()));
+        assert_eq!(list.try_push(3), Ok(()));
+
+        // NOTE: at the limit the element comes back untouched
+        assert_eq!(list.try_push(4), Err(4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+
+        // NOTE: popping frees a slot up again
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.try_push(5), Ok(()));
+        assert_eq!(list.try_push(6), Err(6));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 2, 1]);
+
+        // NOTE: an unbounded list never refuses
+        let mut list = List::new();
+        for i in 0..100 {
+            assert_eq!(list.try_push(i), Ok(()));
+        }
+
+        let mut list = List::with_capacity_limit(0);
+        assert_eq!(list.try_push(1), Err(1));
+    }
+
+    #[test]
+    fn test_len() {
+        // NOTE: the cached len against an actual walk of the list
+        fn check(list: &List<i32>) -> usize {
+            assert_eq!(list.len(), list.iter().count());
+            assert_eq!(list.is_empty(), list.peek().is_none());
+            list.len()
+        }
+
+        let mut list = from_slice(&[5, 1, 3]);
+        assert_eq!(check(&list), 3);
+        list.insert_sorted(2);
+        assert_eq!(check(&list), 4);
+        assert!(list.remove_first(&1));
+        list.push(3);
+        assert_eq!(list.remove_all(&3), 2);
+        assert_eq!(check(&list), 2);
+        list.swap_remove(1);
+        assert_eq!(check(&list), 1);
+
+        list.resize(5, 0);
+        assert_eq!(check(&list), 5);
+        list.resize(2, 0);
+        assert_eq!(check(&list), 2);
+        list.truncate(1);
+        assert_eq!(check(&list), 1);
+
+        list.splice(0, 1, vec![1, 2, 3, 4]);
+        assert_eq!(check(&list), 4);
+        let removed = list.remove_range(1, 3);
+        assert_eq!((check(&list), check(&removed)), (2, 2));
+
+        let list = list.intersperse(0);
+        assert_eq!(check(&list), 3);
+        let list = from_slice(&[1, 2, 3, 4]).take(2);
+        assert_eq!(check(&list), 2);
+        let (_, list) = list.split_last().unwrap();
+        assert_eq!(check(&list), 1);
+
+        let mut list = List::with_capacity_limit(2);
+        for i in 0..5 {
+            list.push(i);
+        }
+        list.push_evicting(5);
+        assert_eq!(check(&list), 2);
+        list.pop();
+        list.pop();
+        assert_eq!(check(&list), 0);
+    }
+
+    #[test]
+    fn test_push_evicting() {
+        let mut list = List::with_capacity_limit(3);
+        assert_eq!(list.push_evicting(1), None);
+        assert_eq!(list.push_evicting(2), None);
+        assert_eq!(list.push_evicting(3), None);
+
+        // NOTE: full, so every push now pushes the oldest one out
+        assert_eq!(list.push_evicting(4), Some(1));
+        assert_eq!(list.push_evicting(5), Some(2));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 4, 3]);
+
+        // NOTE: below the limit again nothing is evicted
+        assert_eq!(list.pop(), Some(5));
+        assert_eq!(list.push_evicting(6), None);
+        assert_eq!(list.push_evicting(7), Some(3));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![7, 6, 4]);
+
+        // NOTE: plain push overfills it, the next evicting push trims back.
+        // 7 and 6 are dropped, only the oldest one is returned.
+        list.push(8);
+        list.push(9);
+        assert_eq!(list.push_evicting(10), Some(4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 9, 8]);
+
+        let mut list = List::with_capacity_limit(1);
+        assert_eq!(list.push_evicting(1), None);
+        assert_eq!(list.push_evicting(2), Some(1));
+        assert_eq!(list.peek(), Some(&2));
+
+        let mut list = List::with_capacity_limit(0);
+        assert_eq!(list.push_evicting(1), Some(1));
+        assert_eq!(list.peek(), None);
+
+        // NOTE: no limit, no eviction
+        let mut list = List::new();
+        for i in 0..10 {
+            assert_eq!(list.push_evicting(i), None);
+        }
+        assert_eq!(list.iter().count(), 10);
+    }
+
+    #[test]
+    fn test_once_repeat() {
+        let mut list = List::once(1);
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+
+        let list = List::repeat(7, 0);
+        assert_eq!(list.peek(), None);
+
+        let list = List::repeat(String::from("x"), 3);
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec!["x", "x", "x"]);
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let list = List::from_fn(4, |i| i * i);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 4, 9]);
+
+        let mut calls = Vec::new();
+        let list = List::from_fn(3, |i| {
+            calls.push(i);
+            i
+        });
+        assert_eq!(calls, vec![0, 1, 2]);
+        assert_eq!(list.peek(), Some(&0));
+
+        let empty = List::from_fn(0, |_: usize| -> i32 { unreachable!() });
+        assert_eq!(empty.peek(), None);
+    }
+
+    #[test]
+    fn test_from_range() {
+        let list = List::from(0..5);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        let list = List::from(2..=4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let empty = List::from(3..3);
+        assert_eq!(empty.peek(), None);
+        assert_eq!(List::from(0..=0).iter().collect::<Vec<_>>(), vec![&0]);
+    }
+
+    #[test]
+    fn test_for_each() {
+        let mut sum = 0;
+        let mut order = Vec::new();
+        from_slice(&[1, 2, 3, 4]).for_each(|x| {
+            sum += x;
+            order.push(x);
+        });
+        assert_eq!(sum, 10);
+        assert_eq!(order, vec![1, 2, 3, 4]);
+
+        List::<i32>::new().for_each(|_| unreachable!());
+    }
+
+    #[test]
+    fn test_try_fold() {
+        let checked_sum = |acc: i32, &x: &i32| if x < 0 { Err(x) } else { Ok(acc + x) };
+
+        assert_eq!(from_slice(&[1, 2, 3]).try_fold(0, checked_sum), Ok(6));
+        assert_eq!(List::new().try_fold(0, checked_sum), Ok(0));
+
+        // NOTE: the first negative one is reported and nothing after it is seen
+        let mut seen = Vec::new();
+        let result = from_slice(&[1, 2, -3, 4, -5]).try_fold(0, |acc, x| {
+            seen.push(*x);
+            checked_sum(acc, x)
+        });
+        assert_eq!(result, Err(-3));
+        assert_eq!(seen, vec![1, 2, -3]);
+    }
+
+    #[test]
+    fn test_fold_right() {
+        let list = from_slice(&[1, 2, 3]);
+
+        let right = list.fold_right(String::from("init"), |x, acc| format!("({}, {})", x, acc));
+        assert_eq!(right, "(1, (2, (3, init)))");
+        let left = list
+            .iter()
+            .fold(String::from("init"), |acc, x| format!("({}, {})", acc, x));
+        assert_eq!(left, "(((init, 1), 2), 3)");
+
+        // NOTE: subtraction isn't associative so the two directions disagree
+        assert_eq!(list.fold_right(0, |x, acc| x - acc), 2);
+        assert_eq!(list.iter().fold(0, |acc, x| acc - x), -6);
+
+        assert_eq!(List::<i32>::new().fold_right(7, |x, acc| x + acc), 7);
+
+        let mut long = List::new();
+        for i in 0..500_000u64 {
+            long.push(i);
+        }
+        assert_eq!(long.fold_right(0, |x, acc| acc + x), 499_999 * 500_000 / 2);
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(from_slice(&[1, 2, 3, 4]).reduce(|a, b| a + b), Some(10));
+        assert_eq!(from_slice(&[3, 9, 2]).reduce(std::cmp::max), Some(9));
+        assert_eq!(from_slice(&[5]).reduce(|_, _| unreachable!()), Some(5));
+        assert_eq!(List::<i32>::new().reduce(|a, b| a + b), None);
+
+        // NOTE: combined in order, not just as a set
+        let words = List::from_fn(3, |i| ["a", "b", "c"][i].to_string());
+        assert_eq!(words.reduce(|a, b| a + &b), Some(String::from("abc")));
+    }
+
+    #[test]
+    fn test_scan() {
+        let sums = from_slice(&[1, 2, 3, 4]).scan(0, |acc, x| {
+            *acc += x;
+            Some(*acc)
+        });
+        assert_eq!(sums.iter().copied().collect::<Vec<_>>(), vec![1, 3, 6, 10]);
+
+        // NOTE: stops at the first None
+        let capped = from_slice(&[1, 2, 3, 4]).scan(0, |acc, x| {
+            *acc += x;
+            (*acc < 6).then(|| acc.to_string())
+        });
+        assert_eq!(capped.iter().collect::<Vec<_>>(), vec!["1", "3"]);
+
+        let empty = List::<i32>::new().scan((), |_, x| Some(x));
+        assert_eq!(empty.peek(), None);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let mut fired = 0;
+        let mut seen = Vec::new();
+        let list = from_slice(&[1, 2, 3]).inspect(|&x| {
+            fired += 1;
+            seen.push(x);
+        });
+        assert_eq!(fired, 3);
+        assert_eq!(seen, vec![1, 2, 3]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let sum: i32 = from_slice(&[1, 2, 3]).inspect(|_| {}).take(2).sum();
+        assert_eq!(sum, 3);
+
+        let mut fired = 0;
+        List::<i32>::new().inspect(|_| fired += 1);
+        assert_eq!(fired, 0);
+    }
+
+    #[test]
+    fn test_interleave() {
+        let list = from_slice(&[1, 3, 5]).interleave(from_slice(&[2, 4, 6]));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+
+        // NOTE: leftovers from whichever side is longer go on the end
+        let list = from_slice(&[1, 3]).interleave(from_slice(&[2, 4, 5, 6]));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        let list = from_slice(&[1, 3, 5, 6]).interleave(from_slice(&[2, 4]));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+
+        let list = List::new().interleave(from_slice(&[1, 2]));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        let list = from_slice(&[1, 2]).interleave(List::new());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(List::<i32>::new().interleave(List::new()).peek(), None);
+    }
+
+    #[test]
+    fn test_zip_with() {
+        let sums = from_slice(&[1, 2, 3]).zip_with(from_slice(&[10, 20, 30]), |a, b| a + b);
+        assert_eq!(sums.iter().copied().collect::<Vec<_>>(), vec![11, 22, 33]);
+
+        // NOTE: the shorter side decides the length, types can differ
+        let labels =
+            from_slice(&[1, 2, 3]).zip_with(from_slice(&["a", "b"]), |n, s| format!("{}{}", s, n));
+        assert_eq!(labels.iter().collect::<Vec<_>>(), vec!["a1", "b2"]);
+
+        let empty = List::<i32>::new().zip_with(from_slice(&[1]), |a, b| a * b);
+        assert_eq!(empty.peek(), None);
+    }
+
+    #[test]
+    fn test_chain() {
+        let a = from_slice(&[1, 2]);
+        let b = from_slice(&[3, 4, 5]);
+        assert_eq!(a.chain(&b).collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5]);
+        assert_eq!(
+            b.chain(&a).copied().collect::<Vec<_>>(),
+            vec![3, 4, 5, 1, 2]
+        );
+
+        // NOTE: both lists are untouched afterwards
+        assert_eq!(a.iter().count() + b.iter().count(), 5);
+
+        let empty = List::new();
+        assert_eq!(a.chain(&empty).copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(empty.chain(&a).copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(empty.chain(&empty).next(), None);
+    }
+
+    #[test]
+    fn test_intersperse() {
+        let list = from_slice(&[1, 2, 3]).intersperse(0);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 0, 2, 0, 3]
+        );
+
+        let list = from_slice(&[1]).intersperse(0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1]);
+
+        let list = List::<i32>::new().intersperse(0);
+        assert_eq!(list.peek(), None);
+
+        let words = List::from_fn(3, |i| ["a", "b", "c"][i].to_string());
+        let joined: String = words.intersperse(String::from(", ")).into_iter().collect();
+        assert_eq!(joined, "a, b, c");
+    }
+
+    #[test]
+    fn test_splice() {
+        let mut list = from_slice(&[1, 2, 3, 4, 5]);
+        list.splice(1, 3, vec![20, 30, 40, 50]);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 20, 30, 40, 50, 4, 5]
+        );
+
+        list.splice(1, 5, Some(0));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 0, 4, 5]);
+
+        // NOTE: an empty range is a plain insert, an empty replacement a removal
+        list.splice(2, 2, vec![2, 3]);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 0, 2, 3, 4, 5]
+        );
+        list.splice(1, 2, None);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+
+        // NOTE: both ends
+        list.splice(0, 1, vec![-1, 0]);
+        list.splice(6, 6, vec![6]);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![-1, 0, 2, 3, 4, 5, 6]
+        );
+        list.splice(0, 7, 10..12);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 11]);
+
+        let mut empty = List::new();
+        empty.splice(0, 0, vec![1, 2]);
+        assert_eq!(empty.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_splice_out_of_bounds() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.splice(2, 4, vec![0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "is after end")]
+    fn test_splice_backwards() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.splice(2, 1, vec![0]);
+    }
+
+    #[test]
+    fn test_remove_range() {
+        let mut list = from_slice(&[1, 2, 3, 4, 5]);
+        let removed = list.remove_range(1, 3);
+        assert_eq!(removed.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 4, 5]);
+
+        let removed = list.remove_range(0, 2);
+        assert_eq!(removed.iter().copied().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5]);
+
+        let removed = list.remove_range(1, 1);
+        assert_eq!(removed.peek(), None);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5]);
+
+        let removed = list.remove_range(0, 1);
+        assert_eq!(removed.iter().copied().collect::<Vec<_>>(), vec![5]);
+        assert_eq!(list.peek(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_remove_range_out_of_bounds() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.remove_range(1, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "is after end")]
+    fn test_remove_range_backwards() {
+        let mut list = from_slice(&[1, 2, 3]);
+        list.remove_range(2, 1);
+    }
+
+    #[test]
+    fn test_split_first() {
+        let Some((head, rest)) = from_slice(&[1, 2, 3]).split_first() else {
+            panic!("list had elements");
+        };
+        assert_eq!(head, 1);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+
+        let (head, rest) = from_slice(&[4]).split_first().unwrap();
+        assert_eq!(head, 4);
+        assert_eq!(rest.peek(), None);
+        assert!(rest.split_first().is_none());
+    }
+
+    #[test]
+    fn test_split_last() {
+        let (last, init) = from_slice(&[1, 2, 3]).split_last().unwrap();
+        assert_eq!(last, 3);
+        assert_eq!(init.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        let (last, init) = from_slice(&[4]).split_last().unwrap();
+        assert_eq!(last, 4);
+        assert_eq!(init.peek(), None);
+        assert!(init.split_last().is_none());
+    }
+
+    #[cfg(feature = "allocator_api")]
+    #[test]
+    fn test_custom_allocator() {
+        use std::alloc::{AllocError, Allocator, Global, Layout};
+        use std::cell::Cell;
+        use std::ptr::NonNull;
+
+        // NOTE: forwards to Global and counts what goes through it
+        #[derive(Clone, Copy)]
+        struct Counting<'a> {
+            allocs: &'a Cell<usize>,
+            frees: &'a Cell<usize>,
+        }
+
+        unsafe impl Allocator for Counting<'_> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.allocs.set(self.allocs.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.frees.set(self.frees.get() + 1);
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let allocs = Cell::new(0);
+        let frees = Cell::new(0);
+        let alloc = Counting {
+            allocs: &allocs,
+            frees: &frees,
+        };
+
+        // NOTE: one allocation per node, all through our allocator
+        let mut list = List::new_in(alloc);
+        for i in (0..5).rev() {
+            list.push(i);
+        }
+        assert_eq!((allocs.get(), frees.get()), (5, 0));
+
+        assert_eq!(list.pop(), Some(0));
+        assert_eq!(frees.get(), 1);
+
+        // NOTE: nodes made anywhere else in the API come from it too
+        list.insert_sorted(3);
+        list.splice(0, 1, vec![10, 11]);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![10, 11, 2, 3, 3, 4]
+        );
+        assert_eq!((allocs.get(), frees.get()), (8, 2));
+
+        // NOTE: and lists split off keep using it
+        let groups = list.group_by(|a, b| a == b);
+        assert_eq!(
+            groups.iter().map(|g| g.iter().count()).collect::<Vec<_>>(),
+            vec![1, 1, 1, 2, 1]
+        );
+        assert_eq!(allocs.get() - frees.get(), 11);
+
+        drop(groups);
+        assert_eq!(allocs.get(), frees.get());
+    }
 }
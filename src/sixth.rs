@@ -0,0 +1,1279 @@
+// NOTE: Production quality unsafe doubly linked deque, the same shape as
+// std's LinkedList. Links are NonNull so Option<Link<T>> stays pointer sized,
+// and the PhantomData tells dropck we own T values even though we only hold
+// pointers, which also makes the list covariant in T like Box would.
+
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+
+pub struct List<T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<T>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+struct Node<T> {
+    front: Link<T>,
+    back: Link<T>,
+    elem: T,
+}
+
+// NOTE: The iterators carry the remaining length, which is what stops them:
+// once the two ends have met len is 0 and neither side yields again, so
+// mixing next and next_back never hands out an element twice.
+pub struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a T>,
+}
+
+pub struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    len: usize,
+    _boo: PhantomData<&'a mut T>,
+}
+
+// NOTE: Sits on a node, or on the ghost between back and front when cur is
+// None, same as std's cursors. Moving off either end lands on the ghost and
+// moving again wraps round to the other end.
+pub struct CursorMut<'a, T> {
+    list: &'a mut List<T>,
+    cur: Link<T>,
+    index: Option<usize>,
+}
+
+// SAFETY: NonNull opts out of Send and Sync, but the list uniquely owns its
+// nodes like a Box would, and the iterators are just &T and &mut T, so they
+// all get the bounds of those.
+unsafe impl<T: Send> Send for List<T> {}
+unsafe impl<T: Sync> Sync for List<T> {}
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+// NOTE: Fail to compile if the auto traits or the variance ever change
+const _: () = {
+    fn assert_send_sync<S: Send + Sync>() {}
+    fn check<T: Send + Sync>() {
+        assert_send_sync::<List<T>>();
+        assert_send_sync::<Iter<'_, T>>();
+        assert_send_sync::<IterMut<'_, T>>();
+    }
+    fn list_covariant<'a, T>(x: List<&'static T>) -> List<&'a T> {
+        x
+    }
+    fn iter_covariant<'i, 'a, T>(x: Iter<'i, &'static T>) -> Iter<'i, &'a T> {
+        x
+    }
+    let _ = check::<i32>;
+    let _ = list_covariant::<i32>;
+    let _ = iter_covariant::<i32>;
+};
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List {
+            front: None,
+            back: None,
+            len: 0,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub fn push_front(&mut self, elem: T) {
+        // SAFETY: new is freshly allocated, and a Some front is a live node we
+        // own. Nothing else holds a reference into the list while &mut self
+        // is borrowed.
+        unsafe {
+            let new = new_node(None, None, elem);
+            if let Some(old) = self.front {
+                (*old.as_ptr()).front = Some(new);
+                (*new.as_ptr()).back = Some(old);
+            } else {
+                // NOTE: Empty list, the new node is both ends
+                self.back = Some(new);
+            }
+            self.front = Some(new);
+            self.len += 1;
+        }
+    }
+
+    pub fn push_back(&mut self, elem: T) {
+        // SAFETY: mirror of push_front
+        unsafe {
+            let new = new_node(None, None, elem);
+            if let Some(old) = self.back {
+                (*old.as_ptr()).back = Some(new);
+                (*new.as_ptr()).front = Some(old);
+            } else {
+                self.front = Some(new);
+            }
+            self.back = Some(new);
+            self.len += 1;
+        }
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: front came from Box::into_raw in a push and is only freed
+        // here or in pop_back, after it has been unlinked from both sides
+        unsafe {
+            self.front.map(|node| {
+                let boxed = Box::from_raw(node.as_ptr());
+                self.front = boxed.back;
+                if let Some(new) = self.front {
+                    (*new.as_ptr()).front = None;
+                } else {
+                    // NOTE: That was the last one, back would dangle
+                    self.back = None;
+                }
+                self.len -= 1;
+                boxed.elem
+            })
+        }
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: mirror of pop_front
+        unsafe {
+            self.back.map(|node| {
+                let boxed = Box::from_raw(node.as_ptr());
+                self.back = boxed.front;
+                if let Some(new) = self.back {
+                    (*new.as_ptr()).back = None;
+                } else {
+                    self.front = None;
+                }
+                self.len -= 1;
+                boxed.elem
+            })
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.front,
+            back: self.back,
+            len: self.len,
+            _boo: PhantomData,
+        }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: self.front,
+            index: self.front.map(|_| 0),
+            list: self,
+        }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            cur: self.back,
+            index: self.back.map(|_| self.len - 1),
+            list: self,
+        }
+    }
+
+    // NOTE: The references borrow self, so no push or pop can free or
+    // rewire the node while they're alive
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: front is a live node owned by the list
+        unsafe { self.front.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in front, and &mut self makes this the only access
+        unsafe { self.front.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: back is a live node owned by the list
+        unsafe { self.back.map(|node| &(*node.as_ptr()).elem) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in back, and &mut self makes this the only access
+        unsafe { self.back.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// NOTE: Pops in a loop so long lists can't blow the stack, and each node is
+// freed exactly once by the pop that unlinks it
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> CursorMut<'_, T> {
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn move_next(&mut self) {
+        // SAFETY: cur is a live node of the list we have borrowed mutably
+        match self.cur {
+            Some(cur) => unsafe {
+                self.cur = (*cur.as_ptr()).back;
+                self.index = match self.cur {
+                    Some(_) => self.index.map(|i| i + 1),
+                    None => None,
+                };
+            },
+            None => {
+                self.cur = self.list.front;
+                self.index = self.cur.map(|_| 0);
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        // SAFETY: mirror of move_next
+        match self.cur {
+            Some(cur) => unsafe {
+                self.cur = (*cur.as_ptr()).front;
+                self.index = match self.cur {
+                    Some(_) => self.index.map(|i| i - 1),
+                    None => None,
+                };
+            },
+            None => {
+                self.cur = self.list.back;
+                self.index = self.cur.map(|_| self.list.len - 1);
+            }
+        }
+    }
+
+    // NOTE: All of these borrow the cursor mutably, so at most one of the
+    // references they hand out can be alive at a time
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: cur is a live node and the cursor has unique access
+        unsafe { self.cur.map(|node| &mut (*node.as_ptr()).elem) }
+    }
+
+    // NOTE: From the ghost the next element is the front
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        // SAFETY: as in current
+        unsafe {
+            let next = match self.cur {
+                Some(cur) => (*cur.as_ptr()).back,
+                None => self.list.front,
+            };
+            next.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    // NOTE: From the ghost the previous element is the back
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        // SAFETY: as in current
+        unsafe {
+            let prev = match self.cur {
+                Some(cur) => (*cur.as_ptr()).front,
+                None => self.list.back,
+            };
+            prev.map(|node| &mut (*node.as_ptr()).elem)
+        }
+    }
+
+    // NOTE: The cursor stays on the same element, which moves up one if
+    // the new one went before it. On the ghost the new element goes on the
+    // back, since that's what comes before the ghost.
+    pub fn insert_before(&mut self, elem: T) {
+        let Some(cur) = self.cur else {
+            self.list.push_back(elem);
+            return;
+        };
+        // SAFETY: cur and its front neighbour are live nodes of the list, the
+        // new node is fresh. The ends are left to push_front.
+        unsafe {
+            match (*cur.as_ptr()).front {
+                Some(prev) => {
+                    let new = new_node(Some(prev), Some(cur), elem);
+                    (*prev.as_ptr()).back = Some(new);
+                    (*cur.as_ptr()).front = Some(new);
+                    self.list.len += 1;
+                }
+                None => self.list.push_front(elem),
+            }
+        }
+        self.index = self.index.map(|i| i + 1);
+    }
+
+    // NOTE: On the ghost the new element goes on the front
+    pub fn insert_after(&mut self, elem: T) {
+        let Some(cur) = self.cur else {
+            self.list.push_front(elem);
+            return;
+        };
+        // SAFETY: mirror of insert_before
+        unsafe {
+            match (*cur.as_ptr()).back {
+                Some(next) => {
+                    let new = new_node(Some(cur), Some(next), elem);
+                    (*next.as_ptr()).front = Some(new);
+                    (*cur.as_ptr()).back = Some(new);
+                    self.list.len += 1;
+                }
+                None => self.list.push_back(elem),
+            }
+        }
+    }
+
+    // NOTE: Moves on to the element after the removed one, or the ghost if
+    // it was the back. On the ghost there's nothing to remove.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let cur = self.cur?;
+        // SAFETY: cur came from Box::into_raw and is only freed here, after
+        // both neighbours (or the list's ends) stop pointing at it
+        unsafe {
+            let boxed = Box::from_raw(cur.as_ptr());
+            match boxed.front {
+                Some(prev) => (*prev.as_ptr()).back = boxed.back,
+                None => self.list.front = boxed.back,
+            }
+            match boxed.back {
+                Some(next) => (*next.as_ptr()).front = boxed.front,
+                None => {
+                    self.list.back = boxed.front;
+                    self.index = None;
+                }
+            }
+            self.cur = boxed.back;
+            self.list.len -= 1;
+            Some(boxed.elem)
+        }
+    }
+}
+
+impl<T> CursorMut<'_, T> {
+    // NOTE: The splices and splits only relink the nodes at the join,
+    // whatever the lengths involved, and leave the cursor on the same
+    // element. On the ghost, before means the back of the list and after
+    // means the front, same as the inserts.
+    pub fn splice_before(&mut self, mut input: List<T>) {
+        let (Some(in_front), Some(in_back)) = (input.front.take(), input.back.take()) else {
+            return;
+        };
+        let in_len = mem::replace(&mut input.len, 0);
+        // SAFETY: cur and its neighbours are live nodes of our list, and the
+        // input's ends are live nodes that list no longer points at, so
+        // every node ends up owned by exactly one list
+        unsafe {
+            match self.cur {
+                Some(cur) => {
+                    match (*cur.as_ptr()).front {
+                        Some(prev) => {
+                            (*prev.as_ptr()).back = Some(in_front);
+                            (*in_front.as_ptr()).front = Some(prev);
+                        }
+                        None => self.list.front = Some(in_front),
+                    }
+                    (*cur.as_ptr()).front = Some(in_back);
+                    (*in_back.as_ptr()).back = Some(cur);
+                    self.index = self.index.map(|i| i + in_len);
+                }
+                None => {
+                    match self.list.back {
+                        Some(back) => {
+                            (*back.as_ptr()).back = Some(in_front);
+                            (*in_front.as_ptr()).front = Some(back);
+                        }
+                        None => self.list.front = Some(in_front),
+                    }
+                    self.list.back = Some(in_back);
+                }
+            }
+        }
+        self.list.len += in_len;
+    }
+
+    pub fn splice_after(&mut self, mut input: List<T>) {
+        let (Some(in_front), Some(in_back)) = (input.front.take(), input.back.take()) else {
+            return;
+        };
+        let in_len = mem::replace(&mut input.len, 0);
+        // SAFETY: mirror of splice_before
+        unsafe {
+            match self.cur {
+                Some(cur) => {
+                    match (*cur.as_ptr()).back {
+                        Some(next) => {
+                            (*next.as_ptr()).front = Some(in_back);
+                            (*in_back.as_ptr()).back = Some(next);
+                        }
+                        None => self.list.back = Some(in_back),
+                    }
+                    (*cur.as_ptr()).back = Some(in_front);
+                    (*in_front.as_ptr()).front = Some(cur);
+                }
+                None => {
+                    match self.list.front {
+                        Some(front) => {
+                            (*front.as_ptr()).front = Some(in_back);
+                            (*in_back.as_ptr()).back = Some(front);
+                        }
+                        None => self.list.back = Some(in_back),
+                    }
+                    self.list.front = Some(in_front);
+                }
+            }
+        }
+        self.list.len += in_len;
+    }
+
+    // NOTE: Everything before the current element, which becomes the front.
+    // On the ghost that's the whole list, like std.
+    pub fn split_before(&mut self) -> List<T> {
+        let Some(cur) = self.cur else {
+            return mem::take(self.list);
+        };
+        // SAFETY: cur and its front neighbour are live nodes of our list, the
+        // cut just moves that side's nodes over to the new list
+        unsafe {
+            let Some(prev) = (*cur.as_ptr()).front.take() else {
+                return List::new();
+            };
+            (*prev.as_ptr()).back = None;
+            let taken = self.index.unwrap();
+            let front = self.list.front.replace(cur);
+            self.list.len -= taken;
+            self.index = Some(0);
+            List {
+                front,
+                back: Some(prev),
+                len: taken,
+                _boo: PhantomData,
+            }
+        }
+    }
+
+    // NOTE: Everything after the current element, which becomes the back.
+    // On the ghost that's the whole list, like std.
+    pub fn split_after(&mut self) -> List<T> {
+        let Some(cur) = self.cur else {
+            return mem::take(self.list);
+        };
+        // SAFETY: mirror of split_before
+        unsafe {
+            let Some(next) = (*cur.as_ptr()).back.take() else {
+                return List::new();
+            };
+            (*next.as_ptr()).front = None;
+            let kept = self.index.unwrap() + 1;
+            let back = self.list.back.replace(cur);
+            let taken = self.list.len - kept;
+            self.list.len = kept;
+            List {
+                front: Some(next),
+                back,
+                len: taken,
+                _boo: PhantomData,
+            }
+        }
+    }
+}
+
+// NOTE: Allocates a node and hands back the raw link, it's freed again by
+// whichever pop or remove_current unlinks it
+fn new_node<T>(front: Link<T>, back: Link<T>, elem: T) -> NonNull<Node<T>> {
+    NonNull::from(Box::leak(Box::new(Node { front, back, elem })))
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: len > 0 so front is a live node, and the list is borrowed
+        // for 'a so it stays that way
+        self.front.map(|node| unsafe {
+            self.len -= 1;
+            self.front = (*node.as_ptr()).back;
+            &(*node.as_ptr()).elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: mirror of next
+        self.back.map(|node| unsafe {
+            self.len -= 1;
+            self.back = (*node.as_ptr()).front;
+            &(*node.as_ptr()).elem
+        })
+    }
+
+    // NOTE: Still O(n) since it has to follow the links, but it just steps
+    // the back pointer instead of making an item for every skipped node.
+    // Skipping past the front exhausts the iterator.
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        // SAFETY: n < len, so all n steps stay on nodes not yet handed out
+        unsafe {
+            for _ in 0..n {
+                self.back = (*self.back?.as_ptr()).front;
+            }
+        }
+        self.len -= n;
+        self.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: as in Iter, and len keeps the two ends from ever reaching
+        // the same node, so every &mut handed out is to a different elem
+        self.front.map(|node| unsafe {
+            self.len -= 1;
+            self.front = (*node.as_ptr()).back;
+            &mut (*node.as_ptr()).elem
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        // SAFETY: mirror of next
+        self.back.map(|node| unsafe {
+            self.len -= 1;
+            self.back = (*node.as_ptr()).front;
+            &mut (*node.as_ptr()).elem
+        })
+    }
+
+    // NOTE: Same as Iter::nth_back
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        // SAFETY: n < len, so all n steps stay on nodes not yet handed out
+        unsafe {
+            for _ in 0..n {
+                self.back = (*self.back?.as_ptr()).front;
+            }
+        }
+        self.len -= n;
+        self.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+
+    #[test]
+    fn basics_front() {
+        let mut list = List::new();
+
+        // NOTE: Check empty list behaves right
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // NOTE: Try to break a one item list
+        list.push_front(10);
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+
+        // NOTE: Mess around
+        list.push_front(10);
+        assert_eq!(list.len(), 1);
+        list.push_front(20);
+        assert_eq!(list.len(), 2);
+        list.push_front(30);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(30));
+        assert_eq!(list.len(), 2);
+        list.push_front(40);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.pop_front(), Some(40));
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.pop_front(), Some(20));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.len(), 0);
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.len(), 0);
+    }
+
+    #[test]
+    fn basics_back() {
+        let mut list = List::new();
+
+        // NOTE: Check empty list behaves right
+        assert_eq!(list.pop_back(), None);
+
+        // NOTE: Populate list
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+
+        // NOTE: Check normal removal
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+
+        // NOTE: Push some more just to make sure nothing's corrupted
+        list.push_back(4);
+        list.push_back(5);
+
+        // NOTE: Check normal removal
+        assert_eq!(list.pop_back(), Some(5));
+        assert_eq!(list.pop_back(), Some(4));
+
+        // NOTE: Check exhaustion
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn transitions() {
+        fn push(list: &mut List<i32>, back: bool, elem: i32) {
+            if back {
+                list.push_back(elem)
+            } else {
+                list.push_front(elem)
+            }
+        }
+        fn pop(list: &mut List<i32>, back: bool) -> Option<i32> {
+            if back {
+                list.pop_back()
+            } else {
+                list.pop_front()
+            }
+        }
+        fn ends(list: &List<i32>) -> (Option<i32>, Option<i32>) {
+            (list.front().copied(), list.back().copied())
+        }
+
+        // NOTE: every way of going 0 -> 1 -> 2 -> 1 -> 0 using either end for
+        // each step, checking both ends every time so a stale link shows up
+        for combo in 0..16 {
+            let [push1, push2, pop1, pop2] = [0, 1, 2, 3].map(|bit| combo & (1 << bit) != 0);
+            let mut list = List::new();
+
+            push(&mut list, push1, 1);
+            assert_eq!(ends(&list), (Some(1), Some(1)));
+            push(&mut list, push2, 2);
+            let (front, back) = if push2 { (1, 2) } else { (2, 1) };
+            assert_eq!(ends(&list), (Some(front), Some(back)));
+            assert_eq!(list.len(), 2);
+
+            let (popped, left) = if pop1 { (back, front) } else { (front, back) };
+            assert_eq!(pop(&mut list, pop1), Some(popped));
+            assert_eq!(ends(&list), (Some(left), Some(left)));
+            assert_eq!(list.len(), 1);
+            assert_eq!(pop(&mut list, pop2), Some(left));
+            assert_eq!(ends(&list), (None, None));
+            assert_eq!(pop(&mut list, pop1), None);
+            assert!(list.is_empty());
+
+            // NOTE: and it still works after being emptied
+            push(&mut list, pop2, 3);
+            assert_eq!(ends(&list), (Some(3), Some(3)));
+        }
+    }
+
+    #[test]
+    fn peeks() {
+        let mut list = List::new();
+        assert_eq!(list.front(), None);
+        assert_eq!(list.back_mut(), None);
+
+        list.push_back(1);
+        list.push_back(2);
+        list.push_back(3);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&3));
+
+        if let Some(x) = list.front_mut() {
+            *x *= 10;
+        }
+        if let Some(x) = list.back_mut() {
+            *x *= 10;
+        }
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.pop_back(), Some(30));
+
+        // NOTE: one element, both ends are the same node
+        if let Some(x) = list.front_mut() {
+            *x += 1;
+        }
+        assert_eq!(list.back(), Some(&3));
+    }
+
+    #[test]
+    fn clear() {
+        let mut list = List::new();
+        for i in 0..10 {
+            list.push_back(i);
+        }
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+        list.push_front(1);
+        assert_eq!(list.pop_back(), Some(1));
+    }
+
+    #[test]
+    fn no_leaks() {
+        use std::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut list = List::new();
+        for _ in 0..5 {
+            list.push_front(Counted(&drops));
+            list.push_back(Counted(&drops));
+        }
+        drop(list.pop_front());
+        drop(list.pop_back());
+        assert_eq!(drops.get(), 2);
+
+        // NOTE: the eight left are each dropped once
+        drop(list);
+        assert_eq!(drops.get(), 10);
+
+        let drops = Cell::new(0);
+        let mut list = List::new();
+        list.push_back(Counted(&drops));
+        list.clear();
+        assert_eq!(drops.get(), 1);
+    }
+
+    #[test]
+    fn long_drop() {
+        // NOTE: Miri is far too slow for the full million
+        let n = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let mut list = List::new();
+        for i in 0..n {
+            list.push_back(i);
+        }
+        assert_eq!(list.len(), n);
+        drop(list);
+    }
+
+    fn filled(n: i32) -> List<i32> {
+        let mut list = List::new();
+        for i in 1..=n {
+            list.push_back(i);
+        }
+        list
+    }
+
+    #[test]
+    fn iter() {
+        let list = filled(4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            vec![4, 3, 2, 1]
+        );
+
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+
+        let mut sum = 0;
+        for x in &list {
+            sum += x;
+        }
+        assert_eq!(sum, 10);
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.iter().next(), None);
+        assert_eq!(empty.iter().next_back(), None);
+        assert_eq!(empty.iter().len(), 0);
+    }
+
+    #[test]
+    fn iter_meet_in_middle() {
+        // NOTE: every mix of front and back calls, for odd and even lengths
+        for n in 0..6 {
+            let list = filled(n);
+            for pattern in 0..(1 << n) {
+                let mut iter = list.iter();
+                let mut front = Vec::new();
+                let mut back = Vec::new();
+                for step in 0..n {
+                    if pattern & (1 << step) == 0 {
+                        front.push(*iter.next().unwrap());
+                    } else {
+                        back.push(*iter.next_back().unwrap());
+                    }
+                    assert_eq!(iter.len(), (n - step - 1) as usize);
+                }
+                assert_eq!(iter.next(), None);
+                assert_eq!(iter.next_back(), None);
+                assert_eq!(iter.next(), None);
+                front.extend(back.into_iter().rev());
+                assert_eq!(front, (1..=n).collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut list = filled(5);
+        for x in list.iter_mut() {
+            *x *= 10;
+        }
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![10, 20, 30, 40, 50]
+        );
+
+        // NOTE: from both ends, holding on to every &mut at once
+        let mut iter = list.iter_mut();
+        let a = iter.next().unwrap();
+        let e = iter.next_back().unwrap();
+        let d = iter.next_back().unwrap();
+        let b = iter.next().unwrap();
+        let c = iter.next_back().unwrap();
+        assert!(iter.next().is_none() && iter.next_back().is_none());
+        assert_eq!(iter.len(), 0);
+        *a += 1;
+        *b += 2;
+        *c += 3;
+        *d += 4;
+        *e += 5;
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![11, 22, 33, 44, 55]
+        );
+
+        for x in &mut list {
+            *x = -*x;
+        }
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            vec![-55, -44, -33, -22, -11]
+        );
+
+        // NOTE: the list is fine to use normally afterwards
+        list.push_front(0);
+        assert_eq!(list.iter_mut().len(), 6);
+        assert_eq!(list.pop_back(), Some(-55));
+    }
+
+    fn both_ways(list: &List<i32>) -> (Vec<i32>, Vec<i32>) {
+        (
+            list.iter().copied().collect(),
+            list.iter().rev().copied().collect(),
+        )
+    }
+
+    #[test]
+    fn cursor_move_peek() {
+        let mut list = filled(6);
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_next(), Some(&mut 2));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.index(), Some(0));
+
+        // NOTE: back past the front onto the ghost
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 6));
+        assert_eq!(cursor.index(), None);
+
+        // NOTE: and on round to the back
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 6));
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        assert_eq!(cursor.index(), Some(5));
+
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(1));
+
+        if let Some(x) = cursor.current() {
+            *x = 20;
+        }
+        if let Some(x) = cursor.peek_next() {
+            *x = 30;
+        }
+        assert_eq!(both_ways(&list).0, vec![1, 20, 30, 4, 5, 6]);
+
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!((cursor.index(), cursor.current()), (Some(5), Some(&mut 6)));
+
+        let mut empty: List<i32> = List::new();
+        let mut cursor = empty.cursor_front_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.peek_next(), None);
+        assert_eq!(cursor.peek_prev(), None);
+    }
+
+    #[test]
+    fn cursor_insert() {
+        let mut list = filled(6);
+
+        // NOTE: seek to the middle and insert both sides
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        cursor.insert_before(7);
+        cursor.insert_after(8);
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 7));
+        assert_eq!(cursor.peek_next(), Some(&mut 8));
+        assert_eq!(list.len(), 8);
+        assert_eq!(both_ways(&list).0, vec![1, 2, 7, 3, 8, 4, 5, 6]);
+
+        // NOTE: at the very ends
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(0);
+        assert_eq!(cursor.index(), Some(1));
+        let mut cursor = list.cursor_back_mut();
+        cursor.insert_after(9);
+        assert_eq!(cursor.index(), Some(8));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 9));
+
+        // NOTE: on the ghost, after is the front and before is the back
+        cursor.move_next();
+        cursor.insert_after(-1);
+        cursor.insert_before(10);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(list.len(), 12);
+        assert_eq!(
+            both_ways(&list),
+            (
+                vec![-1, 0, 1, 2, 7, 3, 8, 4, 5, 6, 9, 10],
+                vec![10, 9, 6, 5, 4, 8, 3, 7, 2, 1, 0, -1]
+            )
+        );
+
+        let mut list = List::new();
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(2);
+        cursor.insert_before(3);
+        cursor.insert_after(1);
+        assert_eq!(cursor.index(), None);
+        assert_eq!(both_ways(&list), (vec![1, 2, 3], vec![3, 2, 1]));
+    }
+
+    #[test]
+    fn cursor_remove() {
+        let mut list = filled(6);
+
+        // NOTE: from the middle
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&mut 4)));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+
+        // NOTE: at the front
+        let mut cursor = list.cursor_front_mut();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 2)));
+        assert_eq!(cursor.peek_prev(), None);
+
+        // NOTE: at the back, which leaves it on the ghost
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(6));
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+
+        // NOTE: on the ghost nothing goes
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(list.len(), 3);
+        assert_eq!(both_ways(&list), (vec![2, 4, 5], vec![5, 4, 2]));
+
+        // NOTE: everything, then reuse
+        let mut cursor = list.cursor_front_mut();
+        while cursor.remove_current().is_some() {}
+        assert_eq!(cursor.index(), None);
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+        list.push_back(1);
+        list.push_front(0);
+        assert_eq!(both_ways(&list), (vec![0, 1], vec![1, 0]));
+    }
+
+    #[test]
+    fn nth_back() {
+        let mut list = filled(6);
+        let model: Vec<i32> = list.iter().copied().collect();
+        for k in 0..8 {
+            assert_eq!(
+                list.iter().nth_back(k),
+                model.len().checked_sub(k + 1).map(|i| &model[i])
+            );
+        }
+
+        // NOTE: carries on from where it stopped, and a skip past the front
+        // leaves it exhausted from both ends
+        let mut iter = list.iter();
+        assert_eq!(iter.nth_back(1), Some(&5));
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.nth_back(1), Some(&2));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        let mut iter = list.iter();
+        iter.next();
+        assert_eq!(iter.nth_back(5), None);
+        assert_eq!((iter.next(), iter.next_back()), (None, None));
+
+        if let Some(x) = list.iter_mut().nth_back(2) {
+            *x *= 10;
+        }
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.nth_back(0), Some(&mut 6));
+        assert_eq!(iter.nth_back(1), Some(&mut 40));
+        assert_eq!(iter.nth_back(9), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn cursor_splice() {
+        let mut three = List::new();
+        for x in 10..13 {
+            three.push_back(x);
+        }
+        let mut list = filled(4);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_after(three);
+        assert_eq!((cursor.index(), cursor.current()), (Some(1), Some(&mut 2)));
+        cursor.splice_before(filled(2));
+        assert_eq!((cursor.index(), cursor.current()), (Some(3), Some(&mut 2)));
+        assert_eq!(list.len(), 9);
+        assert_eq!(
+            both_ways(&list),
+            (
+                vec![1, 1, 2, 2, 10, 11, 12, 3, 4],
+                vec![4, 3, 12, 11, 10, 2, 2, 1, 1]
+            )
+        );
+
+        // NOTE: splicing nothing changes nothing
+        let mut cursor = list.cursor_back_mut();
+        cursor.splice_before(List::new());
+        cursor.splice_after(List::new());
+        assert_eq!((cursor.index(), cursor.current()), (Some(8), Some(&mut 4)));
+
+        // NOTE: at the ends, and on the ghost
+        cursor.splice_after(filled(1));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), None);
+        cursor.splice_after(filled(1));
+        cursor.splice_before(filled(2));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(list.len(), 13);
+        assert_eq!(
+            both_ways(&list).0,
+            vec![1, 1, 1, 2, 2, 10, 11, 12, 3, 4, 1, 1, 2]
+        );
+        assert_eq!(list.back(), Some(&2));
+
+        // NOTE: into an empty list
+        let mut list = List::new();
+        list.cursor_front_mut().splice_before(filled(3));
+        assert_eq!(both_ways(&list), (vec![1, 2, 3], vec![3, 2, 1]));
+        let mut list = List::new();
+        list.cursor_back_mut().splice_after(filled(3));
+        assert_eq!(both_ways(&list), (vec![1, 2, 3], vec![3, 2, 1]));
+    }
+
+    #[test]
+    fn cursor_split() {
+        let mut list = filled(6);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let before = cursor.split_before();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 3)));
+        let after = cursor.split_after();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&mut 3)));
+        assert_eq!(both_ways(&before), (vec![1, 2], vec![2, 1]));
+        assert_eq!(both_ways(&after), (vec![4, 5, 6], vec![6, 5, 4]));
+        assert_eq!((before.len(), after.len(), list.len()), (2, 3, 1));
+        assert_eq!(both_ways(&list), (vec![3], vec![3]));
+
+        // NOTE: at the front nothing is before, at the back nothing is after
+        let mut list = filled(3);
+        let mut cursor = list.cursor_front_mut();
+        assert!(cursor.split_before().is_empty());
+        let mut cursor = list.cursor_back_mut();
+        assert!(cursor.split_after().is_empty());
+        let front = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(both_ways(&front), (vec![1, 2], vec![2, 1]));
+        assert_eq!(both_ways(&list), (vec![3], vec![3]));
+
+        // NOTE: on the ghost either way takes the whole list
+        let mut list = filled(3);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_prev();
+        let all = cursor.split_after();
+        assert_eq!(both_ways(&all), (vec![1, 2, 3], vec![3, 2, 1]));
+        assert!(list.is_empty());
+        let mut list = filled(3);
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        let all = cursor.split_before();
+        assert_eq!(all.len(), 3);
+        assert!(list.is_empty());
+        assert_eq!((list.front(), list.back()), (None, None));
+
+        // NOTE: both halves keep working as lists
+        let mut list = filled(4);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let mut back = cursor.split_after();
+        list.push_back(9);
+        back.push_front(8);
+        assert_eq!(both_ways(&list), (vec![1, 2, 9], vec![9, 2, 1]));
+        assert_eq!(both_ways(&back), (vec![8, 3, 4], vec![4, 3, 8]));
+    }
+
+    #[test]
+    fn cursor_splice_split_no_leaks() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let build = |n| {
+            let mut list = List::new();
+            for _ in 0..n {
+                list.push_back(probe.clone());
+            }
+            list
+        };
+
+        let mut list = build(4);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.splice_after(build(3));
+        cursor.splice_before(build(2));
+        cursor.splice_after(List::new());
+        let before = cursor.split_before();
+        cursor.move_next();
+        let after = cursor.split_after();
+        assert_eq!(before.len() + after.len() + list.len(), 9);
+        assert_eq!(Rc::strong_count(&probe), 10);
+
+        let mut rejoined = before;
+        rejoined.cursor_back_mut().splice_after(list);
+        rejoined.cursor_back_mut().splice_after(after);
+        assert_eq!(rejoined.len(), 9);
+        drop(rejoined);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+}
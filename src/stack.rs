@@ -0,0 +1,140 @@
+// NOTE: The second.rs stack without the heap. Elements live inline in a
+// fixed size array and len says how many of the slots are in use, so it
+// never allocates and fills up at N.
+
+use std::mem::MaybeUninit;
+
+pub struct ArrayStack<T, const N: usize> {
+    // NOTE: slots [0, len) are initialised, the rest are not
+    elems: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> ArrayStack<T, N> {
+    pub const fn new() -> Self {
+        ArrayStack {
+            elems: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    // NOTE: A full stack hands the element back instead of growing
+    pub fn push(&mut self, elem: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(elem);
+        }
+        self.elems[self.len].write(elem);
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the slot was below len so it's initialised, and len has
+        // already moved past it so it is never read again
+        Some(unsafe { self.elems[self.len].assume_init_read() })
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        let top = self.len.checked_sub(1)?;
+        // SAFETY: top < len so the slot is initialised
+        Some(unsafe { self.elems[top].assume_init_ref() })
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        let top = self.len.checked_sub(1)?;
+        // SAFETY: top < len so the slot is initialised
+        Some(unsafe { self.elems[top].assume_init_mut() })
+    }
+}
+
+impl<T, const N: usize> Default for ArrayStack<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayStack<T, N> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ArrayStack;
+
+    #[test]
+    fn basics() {
+        let mut stack: ArrayStack<i32, 4> = ArrayStack::new();
+
+        // NOTE: Check empty stack behaves right
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+
+        // NOTE: Fill to capacity
+        for i in 1..=4 {
+            assert_eq!(stack.push(i), Ok(()));
+        }
+        assert!(stack.is_full());
+        assert_eq!(stack.len(), 4);
+
+        // NOTE: Overflow hands the element back and leaves the stack alone
+        assert_eq!(stack.push(5), Err(5));
+        assert_eq!(stack.peek(), Some(&4));
+
+        if let Some(top) = stack.peek_mut() {
+            *top = 40;
+        }
+
+        // NOTE: Pop back down
+        assert_eq!(stack.pop(), Some(40));
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.push(6), Ok(()));
+        assert_eq!(stack.pop(), Some(6));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut stack: ArrayStack<i32, 0> = ArrayStack::new();
+        assert!(stack.is_full());
+        assert_eq!(stack.push(1), Err(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn drops_what_it_holds() {
+        use std::rc::Rc;
+
+        let probe = Rc::new(());
+        let mut stack: ArrayStack<Rc<()>, 8> = ArrayStack::new();
+        for _ in 0..5 {
+            stack.push(probe.clone()).unwrap();
+        }
+        drop(stack.pop());
+        assert_eq!(Rc::strong_count(&probe), 5);
+
+        // NOTE: only the four live slots get dropped, never the empty ones
+        drop(stack);
+        assert_eq!(Rc::strong_count(&probe), 1);
+    }
+}
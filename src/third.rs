@@ -1,6 +1,12 @@
 //NOTE: Persistent stack
 
-use std::rc::Rc;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::rc::{Rc, Weak};
+
+use crate::second;
 
 pub struct List<T> {
     head: Link<T>,
@@ -42,12 +48,738 @@ impl<T> List<T> {
             next: self.head.as_deref(),
         }
     }
+
+    // NOTE: Goes back round to the head after the last element, forever. Only
+    // an empty list ends it.
+    pub fn cycle(&self) -> Cycle<'_, T> {
+        Cycle {
+            head: self.head.as_deref(),
+            next: self.head.as_deref(),
+        }
+    }
+
+    // NOTE: The handle doesn't cache a length, so these are all plain walks
+    // over the shared nodes. None of them touch a refcount.
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn contains(&self, x: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|elem| elem == x)
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+
+    // NOTE: Only self's spine gets rebuilt, the new last node points straight
+    // at other's head so all of other is shared. An empty self allocates nothing.
+    pub fn append(&self, other: &List<T>) -> List<T>
+    where
+        T: Clone,
+    {
+        List {
+            head: link_from_iter(self.iter().cloned(), other.head.clone()),
+        }
+    }
+
+    pub fn rev(&self) -> List<T>
+    where
+        T: Clone,
+    {
+        let mut head = None;
+        for elem in self.iter() {
+            head = Some(Rc::new(Node {
+                elem: elem.clone(),
+                next: head,
+            }));
+        }
+        List { head }
+    }
+
+    // NOTE: Moves elements out of the nodes we own outright. As soon as we hit
+    // a shared node everything after it is reachable from someone else too,
+    // so the rest of the walk falls back to cloning.
+    pub fn into_rev(mut self) -> List<T>
+    where
+        T: Clone,
+    {
+        let mut head = None;
+        let mut cur_link = self.head.take();
+        while let Some(node) = cur_link {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => {
+                    cur_link = node.next.take();
+                    head = Some(Rc::new(Node {
+                        elem: node.elem,
+                        next: head,
+                    }));
+                }
+                Err(shared) => {
+                    let rest = List { head: Some(shared) };
+                    for elem in rest.iter() {
+                        head = Some(Rc::new(Node {
+                            elem: elem.clone(),
+                            next: head,
+                        }));
+                    }
+                    break;
+                }
+            }
+        }
+        List { head }
+    }
+
+    // NOTE: Path copying: the index nodes in front get copied, the updated
+    // node is new, and everything after it is shared with self.
+    pub fn update(&self, index: usize, value: T) -> Option<List<T>>
+    where
+        T: Clone,
+    {
+        let mut node = self.head.as_ref()?;
+        for _ in 0..index {
+            node = node.next.as_ref()?;
+        }
+        let updated = Some(Rc::new(Node {
+            elem: value,
+            next: node.next.clone(),
+        }));
+        Some(List {
+            head: link_from_iter(self.iter().take(index).cloned(), updated),
+        })
+    }
+
+    // NOTE: index == len is allowed and puts value at the very end
+    pub fn insert_at(&self, index: usize, value: T) -> Option<List<T>>
+    where
+        T: Clone,
+    {
+        let mut suffix = &self.head;
+        for _ in 0..index {
+            suffix = &suffix.as_ref()?.next;
+        }
+        let inserted = Some(Rc::new(Node {
+            elem: value,
+            next: suffix.clone(),
+        }));
+        Some(List {
+            head: link_from_iter(self.iter().take(index).cloned(), inserted),
+        })
+    }
+
+    // NOTE: self still holds the removed node, so it is never uniquely owned
+    // here and the element always comes back as a clone.
+    pub fn remove_at(&self, index: usize) -> Option<(T, List<T>)>
+    where
+        T: Clone,
+    {
+        let mut node = self.head.as_ref()?;
+        for _ in 0..index {
+            node = node.next.as_ref()?;
+        }
+        let list = List {
+            head: link_from_iter(self.iter().take(index).cloned(), node.next.clone()),
+        };
+        Some((node.elem.clone(), list))
+    }
+
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> List<U> {
+        List {
+            head: link_from_iter(self.iter().map(f), None),
+        }
+    }
+
+    // NOTE: If the list ends in a run where every element passes, that run
+    // is shared as-is instead of copied. Finding it takes a first walk, so
+    // pred runs twice for the elements in front of the run.
+    pub fn filter<F: Fn(&T) -> bool>(&self, pred: F) -> List<T>
+    where
+        T: Clone,
+    {
+        let mut run = None;
+        let mut link = &self.head;
+        let mut index = 0;
+        while let Some(node) = link {
+            if !pred(&node.elem) {
+                run = None;
+            } else if run.is_none() {
+                run = Some((index, node));
+            }
+            link = &node.next;
+            index += 1;
+        }
+
+        let (prefix_len, shared) = match run {
+            Some((index, node)) => (index, Some(node.clone())),
+            None => (index, None),
+        };
+        let copied = self
+            .iter()
+            .take(prefix_len)
+            .filter(|elem| pred(elem))
+            .cloned();
+        List {
+            head: link_from_iter(copied, shared),
+        }
+    }
+
+    pub fn zipper(&self) -> Zipper<T> {
+        Zipper {
+            prefix: Vec::new(),
+            suffix: List {
+                head: self.head.clone(),
+            },
+        }
+    }
+
+    // NOTE: A prefix can't be shared, its last node would need a new next
+    pub fn take(&self, n: usize) -> List<T>
+    where
+        T: Clone,
+    {
+        List {
+            head: link_from_iter(self.iter().take(n).cloned(), None),
+        }
+    }
+
+    // NOTE: Just n tail hops, the result is a handle into our own nodes
+    pub fn skip(&self, n: usize) -> List<T> {
+        let mut link = &self.head;
+        for _ in 0..n {
+            match link {
+                Some(node) => link = &node.next,
+                None => break,
+            }
+        }
+        List { head: link.clone() }
+    }
+
+    pub fn zip<U>(&self, other: &List<U>) -> List<(T, U)>
+    where
+        T: Clone,
+        U: Clone,
+    {
+        self.zip_with(other, |a, b| (a.clone(), b.clone()))
+    }
+
+    // NOTE: Stops at the end of the shorter list
+    pub fn zip_with<U, V, F: FnMut(&T, &U) -> V>(&self, other: &List<U>, mut f: F) -> List<V> {
+        List {
+            head: link_from_iter(self.iter().zip(other.iter()).map(|(a, b)| f(a, b)), None),
+        }
+    }
+
+    // NOTE: The front half is copied and the back half is shared with self.
+    // An index past the end clamps, giving (a copy of self, empty).
+    pub fn split_at(&self, index: usize) -> (List<T>, List<T>)
+    where
+        T: Clone,
+    {
+        (self.take(index), self.skip(index))
+    }
+
+    pub fn shares_structure_with(&self, other: &List<T>) -> bool {
+        self.common_suffix_len(other) > 0
+    }
+
+    // NOTE: Counts nodes that are physically shared, not just equal. Two
+    // lists that meet stay together to the end, so once the longer one is
+    // lined up with the shorter a single lockstep walk finds the meeting point.
+    pub fn common_suffix_len(&self, other: &List<T>) -> usize {
+        let (len_a, len_b) = (self.len(), other.len());
+        let mut a = &self.head;
+        let mut b = &other.head;
+        for _ in len_b..len_a {
+            a = &a.as_ref().unwrap().next;
+        }
+        for _ in len_a..len_b {
+            b = &b.as_ref().unwrap().next;
+        }
+
+        let mut remaining = len_a.min(len_b);
+        while let (Some(x), Some(y)) = (a, b) {
+            if Rc::ptr_eq(x, y) {
+                return remaining;
+            }
+            a = &x.next;
+            b = &y.next;
+            remaining -= 1;
+        }
+        0
+    }
+
+    // NOTE: Gathers references and leans on the slice sort, which is a stable
+    // merge sort that runs in a loop, then clones into a fresh list. Only the
+    // Vec of references is extra; the stack stays flat however long we are.
+    pub fn sorted(&self) -> List<T>
+    where
+        T: Ord + Clone,
+    {
+        let mut elems: Vec<&T> = self.iter().collect();
+        elems.sort();
+        List {
+            head: link_from_iter(elems.into_iter().cloned(), None),
+        }
+    }
+
+    // NOTE: Both lists should already be sorted. Ties take from self first,
+    // so the merge is stable. Once one side runs out the rest of the other
+    // side is shared rather than copied.
+    pub fn merge_sorted(&self, other: &List<T>) -> List<T>
+    where
+        T: Ord + Clone,
+    {
+        let mut a = &self.head;
+        let mut b = &other.head;
+        let mut head = None;
+        let mut cur_link = &mut head;
+        while let (Some(x), Some(y)) = (a, b) {
+            let elem = if y.elem < x.elem {
+                b = &y.next;
+                y.elem.clone()
+            } else {
+                a = &x.next;
+                x.elem.clone()
+            };
+            let node = cur_link.insert(Rc::new(Node { elem, next: None }));
+            cur_link = &mut Rc::get_mut(node).unwrap().next;
+        }
+        *cur_link = a.clone().or_else(|| b.clone());
+        List { head }
+    }
+
+    pub fn fold_left<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    // NOTE: The last element has to be seen first, and we can only walk from
+    // the head, so the walk is written down in a Vec of references and
+    // folded backwards. That costs O(n) extra space but keeps the stack flat,
+    // where the recursive version would overflow on long lists.
+    pub fn fold_right<B, F: FnMut(&T, B) -> B>(&self, init: B, mut f: F) -> B {
+        let elems: Vec<&T> = self.iter().collect();
+        elems.into_iter().rev().fold(init, |acc, elem| f(elem, acc))
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// NOTE: Derives would recurse through the nodes, so these all loop instead.
+impl<T: PartialEq> PartialEq for List<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let mut a = &self.head;
+        let mut b = &other.head;
+        loop {
+            match (a, b) {
+                // NOTE: once both walks reach the same node the rest is shared
+                (Some(x), Some(y)) if Rc::ptr_eq(x, y) => return true,
+                (Some(x), Some(y)) => {
+                    if x.elem != y.elem {
+                        return false;
+                    }
+                    a = &x.next;
+                    b = &y.next;
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<T: Eq> Eq for List<T> {}
+
+// NOTE: Only the elements go into the hash, never the node addresses, so
+// equal lists hash the same whether or not they share nodes. The length at
+// the end keeps e.g. [[1], []] and [[], [1]] apart, same as slices do.
+impl<T: Hash> Hash for List<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0;
+        for elem in self.iter() {
+            elem.hash(state);
+            len += 1;
+        }
+        state.write_usize(len);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// NOTE: Classic cons rendering, `1 -> 2 -> 3 -> nil`. The alternate form
+// `{:#}` puts each cell on its own line instead:
+//   1 ->
+//   2 ->
+//   3 ->
+//   nil
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sep = if f.alternate() { " ->\n" } else { " -> " };
+        for elem in self.iter() {
+            write!(f, "{}{}", elem, sep)?;
+        }
+        write!(f, "nil")
+    }
+}
+
+// NOTE: Builds the nodes front to back so the first item ends up as the head,
+// then hangs `tail` off the last node. A freshly made Rc is uniquely owned,
+// so Rc::get_mut lets us link the next node on without reversing anything.
+fn link_from_iter<T, I: IntoIterator<Item = T>>(iter: I, tail: Link<T>) -> Link<T> {
+    let mut head = None;
+    let mut cur_link = &mut head;
+    for elem in iter {
+        let node = cur_link.insert(Rc::new(Node { elem, next: None }));
+        cur_link = &mut Rc::get_mut(node).unwrap().next;
+    }
+    *cur_link = tail;
+    head
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        List {
+            head: link_from_iter(iter, None),
+        }
+    }
+}
+
+// NOTE: Freezing a second::List keeps its order, its head becomes our head
+impl<T> From<second::List<T>> for List<T> {
+    fn from(list: second::List<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+// NOTE: second::List only grows at the front, so these push everything and
+// then flip the links once to get the order back.
+impl<T: Clone> From<&List<T>> for second::List<T> {
+    fn from(list: &List<T>) -> Self {
+        let mut thawed = second::List::new();
+        for elem in list.iter() {
+            thawed.push(elem.clone());
+        }
+        thawed.reverse();
+        thawed
+    }
+}
+
+// NOTE: Moves out of the nodes this handle owns alone, see IntoIter
+impl<T: Clone> From<List<T>> for second::List<T> {
+    fn from(list: List<T>) -> Self {
+        let mut thawed = second::List::new();
+        for elem in list {
+            thawed.push(elem);
+        }
+        thawed.reverse();
+        thawed
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// NOTE: A zipper splits the list at a focus. The nodes already walked past
+// sit on a stack (nearest on top) and the focus is the head of the suffix,
+// so edits at the focus are O(1) and only touch the suffix handle.
+pub struct Zipper<T> {
+    prefix: Vec<Rc<Node<T>>>,
+    suffix: List<T>,
+}
+
+impl<T: Clone> Zipper<T> {
+    pub fn focus(&self) -> Option<&T> {
+        self.suffix.head()
+    }
+
+    // NOTE: Returns false when already past the last element
+    pub fn move_next(&mut self) -> bool {
+        match self.suffix.head.take() {
+            Some(node) => {
+                self.suffix.head = node.next.clone();
+                self.prefix.push(node);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // NOTE: Returns false at the front. If the suffix was edited the node we
+    // step back onto no longer points at it, so it gets copied in front.
+    pub fn move_prev(&mut self) -> bool {
+        match self.prefix.pop() {
+            Some(node) => {
+                self.suffix.head = Some(relink(node, self.suffix.head.take()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // NOTE: Replaces the focus, returns false if there is no focus
+    pub fn set(&mut self, elem: T) -> bool {
+        match self.suffix.head.take() {
+            Some(node) => {
+                self.suffix.head = Some(Rc::new(Node {
+                    elem,
+                    next: node.next.clone(),
+                }));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // NOTE: The new element goes in front of the focus and becomes the focus
+    pub fn insert(&mut self, elem: T) {
+        self.suffix = self.suffix.prepend(elem);
+    }
+
+    // NOTE: Removes the focus, the element after it becomes the new focus
+    pub fn delete(&mut self) -> bool {
+        match self.suffix.head.take() {
+            Some(node) => {
+                self.suffix.head = node.next.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    // NOTE: Walks the stack back to the front. Nodes whose next still points
+    // at what we've rebuilt so far are reused, so only the prefix up to the
+    // last edit gets copied.
+    pub fn rebuild(mut self) -> List<T> {
+        let mut head = self.suffix.head.take();
+        while let Some(node) = self.prefix.pop() {
+            head = Some(relink(node, head));
+        }
+        List { head }
+    }
+}
+
+// NOTE: Puts node in front of next, copying it only if it points elsewhere
+fn relink<T: Clone>(node: Rc<Node<T>>, next: Link<T>) -> Rc<Node<T>> {
+    let unchanged = match (&node.next, &next) {
+        (Some(a), Some(b)) => Rc::ptr_eq(a, b),
+        (None, None) => true,
+        _ => false,
+    };
+    if unchanged {
+        node
+    } else {
+        let copy = Rc::new(Node {
+            elem: node.elem.clone(),
+            next,
+        });
+        // NOTE: run the old node through List's iterative Drop
+        drop(List { head: Some(node) });
+        copy
+    }
+}
+
+impl<T> Drop for Zipper<T> {
+    fn drop(&mut self) {
+        // NOTE: a stack node may be the only owner of a run of deleted nodes,
+        // so let List's Drop free each one rather than the recursive Rc drop
+        while let Some(node) = self.prefix.pop() {
+            drop(List { head: Some(node) });
+        }
+    }
+}
+
+// NOTE: Opt-in list that keeps the hash of every suffix in its node. A
+// prepend hashes just the new element and mixes in the tail's cached hash,
+// so hashing any handle afterwards is O(1), and shared suffixes share their
+// cached hashes too. It's an ordinary List underneath, each element stored
+// alongside the hash of the list starting at that node.
+pub struct HashedList<T> {
+    list: List<(u64, T)>,
+}
+
+impl<T> HashedList<T> {
+    pub fn new() -> Self {
+        HashedList { list: List::new() }
+    }
+
+    pub fn tail(&self) -> HashedList<T> {
+        HashedList {
+            list: self.list.tail(),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.list.head().map(|(_, elem)| elem)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.list.iter().map(|(_, elem)| elem)
+    }
+
+    // NOTE: The empty list hashes to 0
+    pub fn cached_hash(&self) -> u64 {
+        self.list.head().map_or(0, |(hash, _)| *hash)
+    }
+}
+
+impl<T: Hash> HashedList<T> {
+    pub fn prepend(&self, elem: T) -> HashedList<T> {
+        HashedList {
+            list: self
+                .list
+                .prepend((combine_hash(&elem, self.cached_hash()), elem)),
+        }
+    }
+}
+
+// NOTE: DefaultHasher::new always starts from the same keys, so the cached
+// values are stable for the life of the program.
+fn combine_hash<T: Hash>(elem: &T, tail_hash: u64) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    elem.hash(&mut hasher);
+    hasher.write_u64(tail_hash);
+    hasher.finish()
+}
+
+impl<T> Default for HashedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Hash for HashedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.cached_hash());
+    }
+}
+
+// NOTE: Different cached hashes rule equality out straight away, otherwise
+// List's comparison does the rest, fast path for shared nodes included.
+impl<T: PartialEq> PartialEq for HashedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cached_hash() == other.cached_hash() && self.list == other.list
+    }
+}
+
+impl<T: Eq> Eq for HashedList<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for HashedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+// NOTE: Hash-consing. Nodes are keyed on (elem, address of the tail node),
+// so asking for the same cell twice hands back the node already built and
+// structurally equal lists end up pointer-equal. Only Weak references are
+// kept, so nodes still die when the last list using them is dropped; dead
+// entries get pruned from a bucket whenever it's visited, and gc() sweeps
+// the whole table.
+pub struct Interner<T> {
+    state: RandomState,
+    nodes: HashMap<u64, Vec<Weak<Node<T>>>>,
+}
+
+impl<T: Eq + Hash> Interner<T> {
+    pub fn new() -> Self {
+        Interner {
+            state: RandomState::new(),
+            nodes: HashMap::new(),
+        }
+    }
+
+    pub fn prepend(&mut self, tail: &List<T>, elem: T) -> List<T> {
+        let tail_ptr = tail.head.as_ref().map_or(std::ptr::null(), Rc::as_ptr);
+        let key = self.state.hash_one((&elem, tail_ptr));
+        let bucket = self.nodes.entry(key).or_default();
+        bucket.retain(|weak| weak.strong_count() > 0);
+
+        // NOTE: a live node keeps its own next alive, so comparing addresses
+        // against tail can't be fooled by a freed node's address being reused
+        for weak in bucket.iter() {
+            if let Some(node) = weak.upgrade() {
+                let same_tail = node.next.as_ref().map_or(std::ptr::null(), Rc::as_ptr) == tail_ptr;
+                if same_tail && node.elem == elem {
+                    return List { head: Some(node) };
+                }
+            }
+        }
+
+        let node = Rc::new(Node {
+            elem,
+            next: tail.head.clone(),
+        });
+        bucket.push(Rc::downgrade(&node));
+        List { head: Some(node) }
+    }
+
+    pub fn gc(&mut self) {
+        self.nodes.retain(|_, bucket| {
+            bucket.retain(|weak| weak.strong_count() > 0);
+            !bucket.is_empty()
+        });
+    }
+
+    // NOTE: Counts the nodes still alive, dead entries that haven't been
+    // pruned yet are skipped.
+    pub fn len(&self) -> usize {
+        self.nodes
+            .values()
+            .flatten()
+            .filter(|weak| weak.strong_count() > 0)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Eq + Hash> Default for Interner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 pub struct Iter<'a, T> {
     next: Option<&'a Node<T>>,
 }
 
+pub struct Cycle<'a, T> {
+    head: Option<&'a Node<T>>,
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Cycle<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next.or(self.head)?;
+        self.next = node.next.as_deref();
+        Some(&node.elem)
+    }
+}
+
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
@@ -59,6 +791,41 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
+// NOTE: Owning iterator. Elements are moved out of nodes this handle owns
+// outright (Rc::try_unwrap succeeds) and cloned out of nodes someone else
+// can still see. Once one node is shared everything after it is reachable
+// from that other owner too, so from there on every element is a clone.
+// A list that starts unique and ends in a shared tail moves the front and
+// clones the back. Being Rc based, this is 'static but not Send.
+pub struct IntoIter<T>(List<T>);
+
+impl<T: Clone> IntoIterator for List<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+impl<T: Clone> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.0.head.take()?;
+        match Rc::try_unwrap(node) {
+            Ok(mut node) => {
+                self.0.head = node.next.take();
+                Some(node.elem)
+            }
+            Err(node) => {
+                self.0.head = node.next.clone();
+                Some(node.elem.clone())
+            }
+        }
+    }
+}
+
 impl<T> Drop for List<T> {
     fn drop(&mut self) {
         let mut head = self.head.take();
@@ -74,7 +841,19 @@ impl<T> Drop for List<T> {
 
 #[cfg(test)]
 mod test {
-    use super::List;
+    use super::{List, Node};
+    use std::rc::Rc;
+
+    impl<T> List<T> {
+        // NOTE: the node n hops from the head, for checking sharing
+        fn skip_nodes(&self, n: usize) -> &Rc<Node<T>> {
+            let mut node = self.head.as_ref().unwrap();
+            for _ in 0..n {
+                node = node.next.as_ref().unwrap();
+            }
+            node
+        }
+    }
 
     #[test]
     fn test_basics() {
@@ -106,4 +885,937 @@ mod test {
         assert_eq!(iter.next(), Some(&2));
         assert_eq!(iter.next(), Some(&1));
     }
+
+    #[test]
+    fn test_fork() {
+        let base = List::new().prepend(1).prepend(2);
+        let a = base.prepend(3);
+        let b = base.prepend(4);
+
+        // NOTE: base, a and b all point at the same node for 2
+        assert_eq!(Rc::strong_count(base.head.as_ref().unwrap()), 3);
+        assert_eq!(a.tail().head(), Some(&2));
+        assert_eq!(b.tail().head(), Some(&2));
+
+        // NOTE: dropping one side of the fork leaves the shared tail alive
+        drop(a);
+        drop(base);
+        assert_eq!(Rc::strong_count(b.tail().head.as_ref().unwrap()), 2);
+        assert_eq!(b.head(), Some(&4));
+        let b = b.tail();
+        assert_eq!(b.head(), Some(&2));
+        let b = b.tail();
+        assert_eq!(b.head(), Some(&1));
+    }
+
+    #[test]
+    fn test_tail_edges() {
+        // NOTE: tail of the empty list is still the empty list
+        let list: List<i32> = List::new();
+        assert_eq!(list.tail().head(), None);
+
+        // NOTE: tail of a single element list is empty
+        let list = List::new().prepend(1);
+        assert_eq!(list.tail().head(), None);
+        assert_eq!(list.head(), Some(&1));
+    }
+
+    #[test]
+    fn test_from_iter() {
+        // NOTE: the first item collected becomes the head
+        let list: List<i32> = (1..=3).collect();
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        // NOTE: &List works in for loops
+        let mut seen = Vec::new();
+        for elem in &list {
+            seen.push(*elem);
+        }
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_iter_shared() {
+        let base: List<i32> = (1..=3).collect();
+        let a = base.prepend(10);
+        let b = base.prepend(20);
+
+        // NOTE: both walks end with the exact same suffix
+        let a_items: Vec<_> = a.iter().collect();
+        let b_items: Vec<_> = b.iter().collect();
+        assert_eq!(a_items, vec![&10, &1, &2, &3]);
+        assert_eq!(b_items, vec![&20, &1, &2, &3]);
+        assert!(std::ptr::eq(a_items[1], b_items[1]));
+
+        // NOTE: iterating doesn't touch the refcounts
+        assert_eq!(Rc::strong_count(base.head.as_ref().unwrap()), 3);
+    }
+
+    #[test]
+    fn test_iter_empty() {
+        let list: List<i32> = List::new();
+        assert_eq!(list.iter().next(), None);
+
+        let list: List<i32> = std::iter::empty().collect();
+        assert_eq!(list.iter().next(), None);
+    }
+
+    #[test]
+    fn test_long_drop() {
+        // NOTE: a recursive drop would blow the stack long before this
+        let list: List<i32> = (0..1_000_000).collect();
+        drop(list);
+    }
+
+    #[test]
+    fn test_drop_shared() {
+        let base: List<i32> = (1..=3).collect();
+        let a = base.prepend(10);
+        let b = base.prepend(20);
+        drop(base);
+
+        // NOTE: dropping a frees its own node and stops at the shared one
+        drop(a);
+        assert_eq!(
+            Rc::strong_count(b.head.as_ref().unwrap().next.as_ref().unwrap()),
+            1
+        );
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![20, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_queries() {
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert!(!empty.contains(&1));
+        assert_eq!(empty.get(0), None);
+
+        let base: List<i32> = (1..=3).collect();
+        let a = base.prepend(10);
+        let b = a.tail().tail();
+
+        // NOTE: len agrees with a full walk on every handle, shared or not
+        for list in [&base, &a, &b, &a.prepend(20)] {
+            assert_eq!(list.len(), list.iter().count());
+        }
+        assert_eq!(a.len(), 4);
+        assert_eq!(b.len(), 2);
+        assert!(!a.is_empty());
+
+        assert!(a.contains(&10));
+        assert!(a.contains(&3));
+        assert!(!base.contains(&10));
+
+        assert_eq!(a.get(0), Some(&10));
+        assert_eq!(a.get(3), Some(&3));
+        assert_eq!(a.get(4), None);
+        assert_eq!(b.get(0), Some(&2));
+    }
+
+    #[test]
+    fn test_append() {
+        let a: List<i32> = (1..=3).collect();
+        let b: List<i32> = (4..=6).collect();
+
+        let joined = a.append(&b);
+        assert_eq!(
+            joined.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+
+        // NOTE: b's head is now held by b and by joined's third node
+        assert_eq!(Rc::strong_count(b.head.as_ref().unwrap()), 2);
+        // NOTE: a's nodes were copied, not shared
+        assert_eq!(Rc::strong_count(a.head.as_ref().unwrap()), 1);
+
+        // NOTE: originals are untouched
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+
+        // NOTE: appending onto an empty list is just another handle to b
+        let empty: List<i32> = List::new();
+        let same = empty.append(&b);
+        assert!(Rc::ptr_eq(
+            same.head.as_ref().unwrap(),
+            b.head.as_ref().unwrap()
+        ));
+
+        // NOTE: appending an empty list copies self and ends it there
+        let copy = a.append(&empty);
+        assert_eq!(copy.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rev() {
+        let list: List<i32> = (1..=4).collect();
+        let rev = list.rev();
+        assert_eq!(rev.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(
+            rev.rev().iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+
+        // NOTE: reversing one owner of a shared tail leaves the other alone
+        let other = list.prepend(0);
+        let rev = list.into_rev();
+        assert_eq!(rev.iter().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(
+            other.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        let empty: List<i32> = List::new();
+        assert!(empty.rev().is_empty());
+        assert!(empty.into_rev().is_empty());
+
+        let single = List::new().prepend(1);
+        assert_eq!(single.rev().iter().copied().collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_into_rev_moves() {
+        // NOTE: a uniquely owned list must never be cloned by into_rev
+        #[derive(Debug, PartialEq)]
+        struct NoClone(i32);
+        impl Clone for NoClone {
+            fn clone(&self) -> Self {
+                panic!("cloned {}", self.0);
+            }
+        }
+
+        let list: List<NoClone> = (1..=3).map(NoClone).collect();
+        let rev = list.into_rev();
+        assert_eq!(
+            rev.iter().collect::<Vec<_>>(),
+            vec![&NoClone(3), &NoClone(2), &NoClone(1)]
+        );
+    }
+
+    #[test]
+    fn test_update() {
+        let list: List<i32> = (1..=5).collect();
+
+        let updated = list.update(2, 30).unwrap();
+        assert_eq!(
+            updated.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 30, 4, 5]
+        );
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+
+        // NOTE: the node for 4 is literally the same one in both lists
+        let old_four = list.skip_nodes(3);
+        let new_four = updated.skip_nodes(3);
+        assert!(Rc::ptr_eq(old_four, new_four));
+        assert_eq!(Rc::strong_count(old_four), 2);
+
+        // NOTE: the head and last positions work too
+        let updated = list.update(0, 10).unwrap();
+        assert_eq!(
+            updated.iter().copied().collect::<Vec<_>>(),
+            vec![10, 2, 3, 4, 5]
+        );
+        let updated = list.update(4, 50).unwrap();
+        assert_eq!(
+            updated.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 50]
+        );
+
+        assert!(list.update(5, 0).is_none());
+        assert!(List::new().update(0, 0).is_none());
+    }
+
+    #[test]
+    fn test_insert_remove_at() {
+        let list: List<i32> = (1..=4).collect();
+
+        let inserted = list.insert_at(2, 10).unwrap();
+        assert_eq!(
+            inserted.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 10, 3, 4]
+        );
+        // NOTE: 3 onwards is shared with the original
+        assert!(Rc::ptr_eq(inserted.skip_nodes(3), list.skip_nodes(2)));
+
+        let front = list.insert_at(0, 0).unwrap();
+        assert_eq!(
+            front.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        assert!(Rc::ptr_eq(front.skip_nodes(1), list.skip_nodes(0)));
+
+        // NOTE: index == len appends, past that is out of range
+        let back = list.insert_at(4, 5).unwrap();
+        assert_eq!(
+            back.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert!(list.insert_at(5, 6).is_none());
+
+        let (removed, rest) = list.remove_at(1).unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![1, 3, 4]);
+        assert!(Rc::ptr_eq(rest.skip_nodes(1), list.skip_nodes(2)));
+
+        let (removed, rest) = list.remove_at(3).unwrap();
+        assert_eq!(removed, 4);
+        assert_eq!(rest.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(list.remove_at(4).is_none());
+
+        // NOTE: the source list is still intact after all of that
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_map() {
+        let list: List<i32> = (1..=3).collect();
+        let strings = list.map(|x| x.to_string());
+        assert_eq!(
+            strings.iter().cloned().collect::<Vec<_>>(),
+            vec!["1", "2", "3"]
+        );
+
+        let empty: List<i32> = List::new();
+        assert!(empty.map(|x| x * 2).is_empty());
+    }
+
+    #[test]
+    fn test_filter() {
+        let list: List<i32> = (1..=6).collect();
+        let evens = list.filter(|x| x % 2 == 0);
+        assert_eq!(evens.iter().copied().collect::<Vec<_>>(), vec![2, 4, 6]);
+
+        // NOTE: filtering everything out
+        assert!(list.filter(|_| false).is_empty());
+
+        // NOTE: filtering nothing out shares the whole list
+        let all = list.filter(|_| true);
+        assert!(Rc::ptr_eq(all.skip_nodes(0), list.skip_nodes(0)));
+
+        // NOTE: the passing run 4, 5, 6 at the end is shared, the rest copied
+        let big = list.filter(|x| *x != 3);
+        assert_eq!(big.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4, 5, 6]);
+        assert!(Rc::ptr_eq(big.skip_nodes(2), list.skip_nodes(3)));
+        assert!(!Rc::ptr_eq(big.skip_nodes(0), list.skip_nodes(0)));
+    }
+
+    #[test]
+    fn test_eq_fast_path() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static COMPARES: Cell<usize> = const { Cell::new(0) };
+        }
+
+        // NOTE: counts every element comparison
+        struct Probe(i32);
+        impl PartialEq for Probe {
+            fn eq(&self, other: &Self) -> bool {
+                COMPARES.with(|c| c.set(c.get() + 1));
+                self.0 == other.0
+            }
+        }
+
+        let base: List<Probe> = (0..100).map(Probe).collect();
+        let a = base.prepend(Probe(-1));
+        let b = base.prepend(Probe(-1));
+
+        // NOTE: same head, nothing compared
+        assert!(base.tail() == base.tail());
+        assert_eq!(COMPARES.with(|c| c.get()), 0);
+
+        // NOTE: different heads that converge after one node
+        assert!(a == b);
+        assert_eq!(COMPARES.with(|c| c.get()), 1);
+
+        // NOTE: built separately, so every element gets compared
+        COMPARES.with(|c| c.set(0));
+        let c: List<Probe> = (0..100).map(Probe).collect();
+        assert!(c == base);
+        assert_eq!(COMPARES.with(|c| c.get()), 100);
+    }
+
+    #[test]
+    fn test_eq_hash_debug() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of(list: &List<i32>) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            list.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let a: List<i32> = (1..=3).collect();
+        let b = List::new().prepend(3).prepend(2).prepend(1);
+        assert_eq!(a, b);
+        assert_eq!(hash_of(&a), hash_of(&b));
+        assert_ne!(a, a.tail());
+        assert_ne!(a, a.prepend(0));
+        assert_ne!(a, b.update(2, 4).unwrap());
+
+        assert_eq!(format!("{:?}", a), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", List::<i32>::new()), "[]");
+    }
+
+    #[test]
+    fn test_long_eq() {
+        let a: List<i32> = (0..500_000).collect();
+        let b: List<i32> = (0..500_000).collect();
+        assert_eq!(a, b);
+        assert_ne!(a, b.tail());
+    }
+
+    #[test]
+    fn test_zipper() {
+        let list: List<i32> = (1..=6).collect();
+
+        let mut zipper = list.zipper();
+        assert_eq!(zipper.focus(), Some(&1));
+        assert!(!zipper.move_prev());
+
+        // NOTE: walk to the middle and edit there
+        assert!(zipper.move_next());
+        assert!(zipper.move_next());
+        assert_eq!(zipper.focus(), Some(&3));
+        assert!(zipper.set(30));
+        assert!(zipper.move_next());
+        zipper.insert(35);
+        assert_eq!(zipper.focus(), Some(&35));
+        assert!(zipper.move_next());
+        assert!(zipper.delete());
+        assert_eq!(zipper.focus(), Some(&5));
+
+        // NOTE: stepping back over edited nodes keeps the edits
+        assert!(zipper.move_prev());
+        assert!(zipper.move_prev());
+        assert_eq!(zipper.focus(), Some(&30));
+        let edited = zipper.rebuild();
+
+        let expected = list
+            .update(2, 30)
+            .unwrap()
+            .insert_at(3, 35)
+            .unwrap()
+            .remove_at(4)
+            .unwrap()
+            .1;
+        assert_eq!(edited, expected);
+        assert_eq!(
+            edited.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 30, 35, 5, 6]
+        );
+
+        // NOTE: the untouched suffix is shared, the original is unchanged
+        assert!(Rc::ptr_eq(edited.skip_nodes(4), list.skip_nodes(4)));
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_zipper_ends() {
+        let list: List<i32> = (1..=2).collect();
+
+        // NOTE: walking without edits rebuilds the very same list
+        let mut zipper = list.zipper();
+        while zipper.move_next() {}
+        assert_eq!(zipper.focus(), None);
+        assert!(!zipper.set(0));
+        assert!(!zipper.delete());
+        zipper.insert(3);
+        let appended = zipper.rebuild();
+        assert_eq!(appended.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let mut zipper = list.zipper();
+        zipper.move_next();
+        let same = zipper.rebuild();
+        assert!(Rc::ptr_eq(same.skip_nodes(0), list.skip_nodes(0)));
+
+        // NOTE: zipping the empty list
+        let empty: List<i32> = List::new();
+        let mut zipper = empty.zipper();
+        assert!(!zipper.move_next());
+        zipper.insert(1);
+        assert_eq!(
+            zipper.rebuild().iter().copied().collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_into_iter() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static CLONES: Cell<usize> = const { Cell::new(0) };
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Counted(i32);
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.with(|c| c.set(c.get() + 1));
+                Counted(self.0)
+            }
+        }
+
+        // NOTE: uniquely owned, every element is moved out
+        let list: List<Counted> = (1..=4).map(Counted).collect();
+        let items: Vec<_> = list.into_iter().map(|c| c.0).collect();
+        assert_eq!(items, vec![1, 2, 3, 4]);
+        assert_eq!(CLONES.with(|c| c.get()), 0);
+
+        // NOTE: fully shared, every element is cloned and other is untouched
+        let list: List<Counted> = (1..=4).map(Counted).collect();
+        let other = list.tail().prepend(Counted(1));
+        let items: Vec<_> = list.tail().into_iter().map(|c| c.0).collect();
+        assert_eq!(items, vec![2, 3, 4]);
+        assert_eq!(CLONES.with(|c| c.get()), 3);
+        assert_eq!(
+            other.iter().map(|c| c.0).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+
+        // NOTE: unique front, shared back: 10 and 20 move, the rest clone
+        CLONES.with(|c| c.set(0));
+        let mixed = other.tail().prepend(Counted(20)).prepend(Counted(10));
+        let items: Vec<_> = mixed.into_iter().map(|c| c.0).collect();
+        assert_eq!(items, vec![10, 20, 2, 3, 4]);
+        assert_eq!(CLONES.with(|c| c.get()), 3);
+        assert_eq!(
+            other.iter().map(|c| c.0).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+
+        drop(list);
+    }
+
+    #[test]
+    fn test_take_skip() {
+        let list: List<i32> = (1..=5).collect();
+
+        assert_eq!(list.take(2).iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(
+            list.skip(2).iter().copied().collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+
+        // NOTE: skip hands out existing nodes, nothing new is allocated
+        let skipped = list.skip(2);
+        assert!(Rc::ptr_eq(skipped.skip_nodes(0), list.skip_nodes(2)));
+        assert_eq!(Rc::strong_count(list.skip_nodes(2)), 2);
+        let same = list.skip(0);
+        assert!(Rc::ptr_eq(same.skip_nodes(0), list.skip_nodes(0)));
+
+        assert!(list.skip(5).is_empty());
+        assert!(list.skip(50).is_empty());
+        assert!(list.take(0).is_empty());
+        assert_eq!(list.take(50), list);
+
+        // NOTE: take(n) then skip(n) puts the original back together
+        for n in 0..=6 {
+            assert_eq!(list.take(n).append(&list.skip(n)), list);
+        }
+    }
+
+    #[test]
+    fn test_zip() {
+        let nums: List<i32> = (1..=4).collect();
+        let names: List<&str> = ["a", "b", "c"].into_iter().collect();
+
+        let zipped = nums.zip(&names);
+        assert_eq!(
+            zipped.iter().cloned().collect::<Vec<_>>(),
+            vec![(1, "a"), (2, "b"), (3, "c")]
+        );
+        assert_eq!(names.zip(&nums).len(), 3);
+
+        let empty: List<i32> = List::new();
+        assert!(empty.zip(&names).is_empty());
+        assert!(names.zip(&empty).is_empty());
+
+        let a_vec = [1, 5, 9, 13];
+        let b_vec = [2, 4, 6, 8, 10];
+        let a: List<i32> = a_vec.iter().copied().collect();
+        let b: List<i32> = b_vec.iter().copied().collect();
+        let expected: Vec<i32> = a_vec.iter().zip(&b_vec).map(|(x, y)| x + y).collect();
+        assert_eq!(
+            a.zip_with(&b, |x, y| x + y)
+                .iter()
+                .copied()
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_split_at() {
+        let list: List<i32> = (1..=5).collect();
+
+        let (front, back) = list.split_at(2);
+        assert_eq!(front.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        // NOTE: back is held by list's node for 2 and by the back handle
+        assert_eq!(Rc::strong_count(back.skip_nodes(0)), 2);
+        assert!(Rc::ptr_eq(back.skip_nodes(0), list.skip_nodes(2)));
+
+        let (front, back) = list.split_at(0);
+        assert!(front.is_empty());
+        assert!(Rc::ptr_eq(back.skip_nodes(0), list.skip_nodes(0)));
+
+        let (front, back) = list.split_at(5);
+        assert_eq!(front, list);
+        assert!(back.is_empty());
+
+        // NOTE: past the end clamps
+        let (front, back) = list.split_at(9);
+        assert_eq!(front, list);
+        assert!(back.is_empty());
+    }
+
+    #[test]
+    fn test_second_conversions() {
+        use crate::second;
+
+        let mut mutable = second::List::new();
+        mutable.push(3);
+        mutable.push(2);
+        mutable.push(1);
+
+        // NOTE: order is kept going both ways
+        let frozen = List::from(mutable);
+        assert_eq!(frozen.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let thawed = second::List::from(&frozen);
+        assert_eq!(thawed.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let thawed = second::List::from(frozen);
+        assert_eq!(thawed.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let empty: List<i32> = List::from(second::List::new());
+        assert!(empty.is_empty());
+        assert_eq!(second::List::from(empty).peek(), None);
+    }
+
+    #[test]
+    fn test_second_conversion_moves() {
+        use crate::second;
+        use std::cell::Cell;
+
+        thread_local! {
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        // NOTE: panics on clone and counts drops, so a move is the only way out
+        struct Tracked;
+        impl Clone for Tracked {
+            fn clone(&self) -> Self {
+                panic!("cloned");
+            }
+        }
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPS.with(|d| d.set(d.get() + 1));
+            }
+        }
+
+        let frozen: List<Tracked> = (0..5).map(|_| Tracked).collect();
+        let thawed = second::List::from(frozen);
+        assert_eq!(DROPS.with(|d| d.get()), 0);
+        assert_eq!(thawed.iter().count(), 5);
+
+        drop(thawed);
+        assert_eq!(DROPS.with(|d| d.get()), 5);
+    }
+
+    #[test]
+    fn test_common_suffix() {
+        let base: List<i32> = (1..=3).collect();
+        let a = base.prepend(10);
+        let b = base.prepend(20).prepend(30);
+
+        assert!(a.shares_structure_with(&b));
+        assert_eq!(a.common_suffix_len(&b), 3);
+        assert_eq!(b.common_suffix_len(&a), 3);
+        assert_eq!(a.common_suffix_len(&base), 3);
+
+        // NOTE: equal contents but built separately share nothing
+        let c: List<i32> = (1..=3).collect();
+        assert_eq!(c, base);
+        assert!(!c.shares_structure_with(&base));
+        assert_eq!(c.common_suffix_len(&base), 0);
+
+        // NOTE: identical handles share everything
+        let same = a.skip(0);
+        assert_eq!(a.common_suffix_len(&same), 4);
+
+        let empty: List<i32> = List::new();
+        assert!(!empty.shares_structure_with(&a));
+        assert!(!empty.shares_structure_with(&List::new()));
+    }
+
+    #[test]
+    fn test_display() {
+        let empty: List<i32> = List::new();
+        assert_eq!(format!("{}", empty), "nil");
+        assert_eq!(format!("{:#}", empty), "nil");
+
+        let single = List::new().prepend(1);
+        assert_eq!(format!("{}", single), "1 -> nil");
+        assert_eq!(format!("{:#}", single), "1 ->\nnil");
+
+        let list: List<i32> = (1..=3).collect();
+        assert_eq!(format!("{}", list), "1 -> 2 -> 3 -> nil");
+        assert_eq!(format!("{:#}", list), "1 ->\n2 ->\n3 ->\nnil");
+    }
+
+    #[test]
+    fn test_hashed_list() {
+        use super::HashedList;
+        use std::cell::Cell;
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+        use std::hash::{Hash, Hasher};
+
+        thread_local! {
+            static HASHES: Cell<usize> = const { Cell::new(0) };
+        }
+
+        // NOTE: counts every time an element gets hashed
+        #[derive(PartialEq, Eq, Debug)]
+        struct Probe(i32);
+        impl Hash for Probe {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                HASHES.with(|c| c.set(c.get() + 1));
+                self.0.hash(state);
+            }
+        }
+
+        let list = HashedList::new()
+            .prepend(Probe(3))
+            .prepend(Probe(2))
+            .prepend(Probe(1));
+        assert_eq!(HASHES.with(|c| c.get()), 3);
+
+        // NOTE: the cached value matches hashing everything from scratch,
+        // back to front, each element followed by the hash of what's after it
+        let mut uncached = 0;
+        for x in [3, 2, 1] {
+            let mut hasher = DefaultHasher::new();
+            x.hash(&mut hasher);
+            hasher.write_u64(uncached);
+            uncached = hasher.finish();
+        }
+        assert_eq!(list.cached_hash(), uncached);
+
+        // NOTE: hashing the list again never touches the elements
+        HASHES.with(|c| c.set(0));
+        let mut set = HashSet::new();
+        set.insert(list.tail());
+        set.insert(list.tail());
+        assert!(set.contains(&list.tail()));
+        assert_eq!(set.len(), 1);
+        assert_eq!(HASHES.with(|c| c.get()), 0);
+
+        // NOTE: a tail's hash is the one its node cached on the way up
+        let again = HashedList::new().prepend(Probe(3)).prepend(Probe(2));
+        assert_eq!(again, list.tail());
+        assert_eq!(again.cached_hash(), list.tail().cached_hash());
+        assert_ne!(again, list);
+        assert_ne!(list.prepend(Probe(0)).cached_hash(), list.cached_hash());
+        assert_eq!(HashedList::<Probe>::new().cached_hash(), 0);
+        assert_eq!(list.tail().tail().tail(), HashedList::new());
+
+        assert_eq!(format!("{:?}", list), "[Probe(1), Probe(2), Probe(3)]");
+    }
+
+    #[test]
+    fn test_sorted() {
+        // NOTE: ordered by key only, so tag shows whether ties kept their order
+        #[derive(Clone, Debug)]
+        struct Keyed {
+            key: i32,
+            tag: char,
+        }
+        impl PartialEq for Keyed {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+        impl Eq for Keyed {}
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.key.cmp(&other.key)
+            }
+        }
+
+        let list: List<Keyed> = [(3, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')]
+            .into_iter()
+            .map(|(key, tag)| Keyed { key, tag })
+            .collect();
+        let sorted = list.sorted();
+        assert_eq!(
+            sorted.iter().map(|k| (k.key, k.tag)).collect::<Vec<_>>(),
+            vec![(1, 'b'), (1, 'e'), (2, 'd'), (3, 'a'), (3, 'c')]
+        );
+        // NOTE: the original keeps its order
+        assert_eq!(list.iter().map(|k| k.tag).collect::<String>(), "abcde");
+
+        let empty: List<i32> = List::new();
+        assert!(empty.sorted().is_empty());
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let a: List<i32> = [1, 4, 4, 9].into_iter().collect();
+        let b: List<i32> = [2, 4, 5, 10, 11, 12].into_iter().collect();
+
+        let merged = a.merge_sorted(&b);
+        assert_eq!(
+            merged.iter().copied().collect::<Vec<_>>(),
+            vec![1, 2, 4, 4, 4, 5, 9, 10, 11, 12]
+        );
+        // NOTE: b's 10, 11, 12 are shared once a runs out
+        assert_eq!(merged.common_suffix_len(&b), 3);
+        assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 4, 4, 9]);
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.merge_sorted(&b), b);
+        assert_eq!(b.merge_sorted(&empty).common_suffix_len(&b), 6);
+    }
+
+    #[test]
+    fn test_long_sort() {
+        let list: List<i32> = (0..200_000).rev().collect();
+        let sorted = list.sorted();
+        assert!(sorted
+            .iter()
+            .zip(sorted.iter().skip(1))
+            .all(|(a, b)| a <= b));
+        assert_eq!(sorted.head(), Some(&0));
+        assert_eq!(sorted.len(), 200_000);
+    }
+
+    #[test]
+    fn test_interner() {
+        use super::Interner;
+        use std::cell::Cell;
+        use std::hash::{Hash, Hasher};
+
+        thread_local! {
+            static COMPARES: Cell<usize> = const { Cell::new(0) };
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        #[derive(Debug)]
+        struct Probe(i32);
+        impl PartialEq for Probe {
+            fn eq(&self, other: &Self) -> bool {
+                COMPARES.with(|c| c.set(c.get() + 1));
+                self.0 == other.0
+            }
+        }
+        impl Eq for Probe {}
+        impl Hash for Probe {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+        impl Drop for Probe {
+            fn drop(&mut self) {
+                DROPS.with(|d| d.set(d.get() + 1));
+            }
+        }
+
+        fn build(interner: &mut Interner<Probe>, items: &[i32]) -> List<Probe> {
+            let mut list = List::new();
+            for x in items.iter().rev() {
+                list = interner.prepend(&list, Probe(*x));
+            }
+            list
+        }
+
+        let mut interner = Interner::new();
+        let a = build(&mut interner, &[1, 2, 3]);
+        let b = build(&mut interner, &[1, 2, 3]);
+        let c = build(&mut interner, &[0, 2, 3]);
+        assert!(Rc::ptr_eq(a.skip_nodes(0), b.skip_nodes(0)));
+        assert!(Rc::ptr_eq(a.skip_nodes(1), c.skip_nodes(1)));
+        assert_eq!(interner.len(), 4);
+
+        // NOTE: equal interned lists compare by pointer, no elements looked at
+        COMPARES.with(|c| c.set(0));
+        assert_eq!(a, b);
+        assert_eq!(COMPARES.with(|c| c.get()), 0);
+
+        // NOTE: the duplicate Probes passed in for b and c were dropped on the spot
+        assert_eq!(DROPS.with(|d| d.get()), 5);
+
+        // NOTE: once all handles are gone the nodes are freed, the interner
+        // only held weak references to them
+        drop(a);
+        drop(b);
+        drop(c);
+        assert_eq!(DROPS.with(|d| d.get()), 9);
+        assert!(interner.is_empty());
+        interner.gc();
+        assert!(interner.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_folds() {
+        let list: List<i32> = (1..=4).collect();
+
+        // NOTE: a right fold of prepend rebuilds the list as it was
+        let rebuilt = list.fold_right(List::new(), |x, acc| acc.prepend(*x));
+        assert_eq!(rebuilt, list);
+        // NOTE: a left fold of prepend reverses it
+        let reversed = list.fold_left(List::new(), |acc, x| acc.prepend(*x));
+        assert_eq!(reversed, list.rev());
+
+        let right = list.fold_right(String::new(), |x, acc| format!("({} {})", x, acc));
+        assert_eq!(right, "(1 (2 (3 (4 ))))");
+        let left = list.fold_left(String::new(), |acc, x| format!("({} {})", acc, x));
+        assert_eq!(left, "(((( 1) 2) 3) 4)");
+    }
+
+    #[test]
+    fn test_long_fold_right() {
+        let list: List<u64> = (0..500_000).collect();
+        assert_eq!(list.fold_right(0, |x, acc| acc + x), 499_999 * 500_000 / 2);
+        let rebuilt = list.fold_right(List::new(), |x, acc| acc.prepend(*x));
+        assert_eq!(rebuilt, list);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let list: List<i32> = (1..=3).collect();
+        let taken: Vec<i32> = list.cycle().take(7).copied().collect();
+        assert_eq!(taken, vec![1, 2, 3, 1, 2, 3, 1]);
+
+        // NOTE: a tail shares the nodes but cycles round its own head
+        let tail = list.tail();
+        assert_eq!(
+            tail.cycle().take(5).copied().collect::<Vec<_>>(),
+            vec![2, 3, 2, 3, 2]
+        );
+
+        let one = List::new().prepend('x');
+        assert_eq!(one.cycle().take(3).collect::<String>(), "xxx");
+
+        let empty: List<i32> = List::new();
+        assert_eq!(empty.cycle().next(), None);
+    }
 }
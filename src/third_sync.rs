@@ -0,0 +1,205 @@
+//NOTE: Persistent stack that can be shared across threads.
+// Same shape as third.rs, just with Arc in place of Rc.
+
+use std::sync::Arc;
+
+pub struct List<T> {
+    head: Link<T>,
+}
+
+type Link<T> = Option<Arc<Node<T>>>;
+
+struct Node<T> {
+    elem: T,
+    next: Link<T>,
+}
+
+// NOTE: Arc<Node<T>> is Send + Sync exactly when T is, so the list picks
+// that up automatically. These fail to compile if that ever changes.
+const _: () = {
+    fn assert_send_sync<S: Send + Sync>() {}
+    fn check<T: Send + Sync>() {
+        assert_send_sync::<List<T>>();
+        assert_send_sync::<Iter<'_, T>>();
+    }
+    let _ = check::<i32>;
+};
+
+impl<T> List<T> {
+    pub fn new() -> Self {
+        List { head: None }
+    }
+
+    pub fn prepend(&self, elem: T) -> List<T> {
+        List {
+            head: Some(Arc::new(Node {
+                elem,
+                next: self.head.clone(),
+            })),
+        }
+    }
+
+    pub fn tail(&self) -> List<T> {
+        List {
+            head: self.head.as_ref().and_then(|node| node.next.clone()),
+        }
+    }
+
+    pub fn head(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.elem)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+}
+
+impl<T> Default for List<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// NOTE: Lists made by cloning a handle share every node, same as tail()
+impl<T> Clone for List<T> {
+    fn clone(&self) -> Self {
+        List {
+            head: self.head.clone(),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut head = None;
+        let mut cur_link = &mut head;
+        for elem in iter {
+            let node = cur_link.insert(Arc::new(Node { elem, next: None }));
+            cur_link = &mut Arc::get_mut(node).unwrap().next;
+        }
+        List { head }
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.elem
+        })
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        let mut head = self.head.take();
+        // NOTE: Arc::try_unwrap isn't enough here. Two threads can both see a
+        // count of 2, both fail, and then whichever decrements last frees the
+        // node through the recursive drop. Arc::into_inner hands the node to
+        // exactly one of the last owners, so the loop keeps going on that thread.
+        while let Some(node) = head {
+            if let Some(mut node) = Arc::into_inner(node) {
+                head = node.next.take();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::List;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_basics() {
+        let list = List::new();
+        assert_eq!(list.head(), None);
+
+        let list = list.prepend(1).prepend(2).prepend(3);
+        assert_eq!(list.head(), Some(&3));
+
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&2));
+        let list = list.tail();
+        assert_eq!(list.head(), Some(&1));
+
+        let list = list.tail();
+        assert_eq!(list.head(), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let list = List::new().prepend(1).prepend(2).prepend(3);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_threads() {
+        // NOTE: build on this thread, then read and drop on four others
+        let list: List<i32> = (0..1000).collect();
+        let list = list.prepend(-1);
+
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let list = list.prepend(i);
+                thread::spawn(move || {
+                    assert_eq!(list.head(), Some(&i));
+                    list.tail().iter().sum::<i32>()
+                })
+            })
+            .collect();
+
+        let expected: i32 = (0..1000).sum::<i32>() - 1;
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+
+        // NOTE: the other threads dropped their handles, ours is the last one
+        assert_eq!(Arc::strong_count(list.head.as_ref().unwrap()), 1);
+        assert_eq!(list.iter().count(), 1001);
+    }
+
+    #[test]
+    fn test_concurrent_drop() {
+        // NOTE: every thread races to drop its handle to the same long chain.
+        // Whichever one ends up last has to free it all without recursing.
+        for _ in 0..10 {
+            let list: List<i32> = (0..100_000).collect();
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    let list = list.clone();
+                    thread::spawn(move || drop(list))
+                })
+                .collect();
+            drop(list);
+            for handle in handles {
+                handle.join().unwrap();
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+// NOTE: Lives in its own test binary because the counting allocator below
+// replaces the global allocator for every test in the binary it's built into.
+
+use linked_lists::second::List;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// NOTE: counts the allocations made on the current thread, so tests
+// running in parallel don't show up in each other's numbers
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|a| a.set(a.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocs() -> usize {
+    ALLOCS.with(|a| a.get())
+}
+
+#[test]
+fn test_recycling() {
+    let mut list = List::recycling(4);
+
+    // NOTE: warm the pool up
+    for i in 0..4 {
+        list.push(i);
+    }
+    for _ in 0..4 {
+        list.pop();
+    }
+
+    // NOTE: from here on push/pop churn never hits the allocator
+    let before = allocs();
+    for _ in 0..100 {
+        for i in 0..4 {
+            list.push(i);
+        }
+        for i in (0..4).rev() {
+            assert_eq!(list.pop(), Some(i));
+        }
+    }
+    assert_eq!(allocs(), before);
+
+    // NOTE: going past the cap allocates for the extra nodes only
+    let before = allocs();
+    for i in 0..6 {
+        list.push(i);
+    }
+    assert_eq!(allocs() - before, 2);
+    while list.pop().is_some() {}
+
+    // NOTE: once the pool is released pushes allocate again
+    list.shrink_pool();
+    let before = allocs();
+    list.push(1);
+    assert_eq!(allocs() - before, 1);
+
+    // NOTE: a plain list allocates on every push
+    let mut plain = List::new();
+    let before = allocs();
+    for i in 0..10 {
+        plain.push(i);
+        plain.pop();
+    }
+    assert_eq!(allocs() - before, 10);
+}
+
+#[test]
+fn test_push_evicting_recycles() {
+    let mut list = List::recycling_with_capacity_limit(1, 3);
+    for i in 0..3 {
+        assert_eq!(list.push_evicting(i), None);
+    }
+
+    // NOTE: each push takes the slot the previous eviction left behind
+    assert_eq!(list.push_evicting(3), Some(0));
+    let before = allocs();
+    for i in 4..100 {
+        assert_eq!(list.push_evicting(i), Some(i - 3));
+    }
+    assert_eq!(allocs(), before);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![99, 98, 97]);
+
+    // NOTE: several evicted at once keep only as many as the pool holds
+    let mut list = List::recycling_with_capacity_limit(2, 1);
+    for i in 0..4 {
+        list.push(i);
+    }
+    assert_eq!(list.push_evicting(4), Some(0));
+    let before = allocs();
+    list.push(5);
+    list.push(6);
+    assert_eq!(allocs(), before);
+    list.push(7);
+    assert_eq!(allocs() - before, 1);
+}
+
+#[test]
+fn test_truncate_recycles() {
+    let mut list = List::recycling(4);
+    for i in 0..6 {
+        list.push(i);
+    }
+
+    // NOTE: the pool keeps four of the five freed nodes, the fifth is freed
+    list.truncate(1);
+    assert_eq!(list.len(), 1);
+    let before = allocs();
+    for i in 0..4 {
+        list.insert_sorted(i);
+    }
+    assert_eq!(allocs(), before);
+    list.push(9);
+    assert_eq!(allocs() - before, 1);
+
+    // NOTE: take and resize go through the same pool
+    let mut list = list.take(2);
+    let before = allocs();
+    list.resize(5, 7);
+    assert_eq!(allocs(), before);
+    assert_eq!(list.len(), 5);
+}
+
+#[test]
+fn test_splice_recycles() {
+    let mut list = List::recycling(4);
+    for i in 0..6 {
+        list.push(i);
+    }
+
+    // NOTE: the replacement reuses the nodes it displaces
+    let before = allocs();
+    list.splice(1, 4, [10, 11, 12]);
+    assert_eq!(allocs(), before);
+    assert_eq!(list.len(), 6);
+
+    // NOTE: a longer replacement allocates for the extra nodes only
+    let before = allocs();
+    list.splice(0, 2, [20, 21, 22, 23]);
+    assert_eq!(allocs() - before, 2);
+    assert_eq!(list.len(), 8);
+
+    // NOTE: remove_all hands its matches back too, intersperse takes them
+    let mut list = List::recycling(4);
+    for i in [1, 2, 1, 2, 1] {
+        list.push(i);
+    }
+    assert_eq!(list.remove_all(&1), 3);
+    let before = allocs();
+    let list = list.intersperse(0);
+    assert_eq!(allocs(), before);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 0, 2]);
+}